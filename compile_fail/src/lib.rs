@@ -0,0 +1,29 @@
+//! Compile-fail cases are emitted as doctests, which `rustdoc` only runs for libraries,
+//! so the example is included here, to be run by `cargo test --doc`,
+//! rather than only being compiled as a test of `test_gen` itself.
+include!("../../tests/doctest_example_compile_fail.rs");
+
+/// States of connections which can be encoded, which closed connections can't be.
+pub trait Encodable {}
+
+impl Encodable for Open {}
+
+pub fn encode<State: Encodable>(_: &Connection<State>) -> &'static str {
+    "open"
+}
+
+pub fn decode(encoded: &str) -> Option<Connection<Open>> {
+    (encoded == "open").then_some(Connection(Open))
+}
+
+// Compile-fail cases of round-trip blocks are compiled calling the encoding function
+// with a reference to their value, as their test would be
+// (e.g. "the trait bound `Closed: Encodable` is not satisfied", rather than mismatched types)
+test_gen! {
+    roundtrip fn crate::encode, fn crate::decode => {
+        closed_encoded: {
+            #[compile_fail(E0277)]
+            (crate::Connection::connect())
+        },
+    }
+}
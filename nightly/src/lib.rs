@@ -0,0 +1,58 @@
+//! Nightly benchmarks require `#![feature(test)]`, which only nightly toolchains accept,
+//! so they're built here, outside the workspace of `test_gen`,
+//! by `cargo +nightly test --manifest-path nightly/Cargo.toml`,
+//! which also runs each benchmark once, alongside the tests of every case.
+#![feature(test)]
+
+extern crate test;
+
+#[cfg(test)]
+mod benches {
+    use test_gen::test_gen;
+
+    fn validate(input: &str) {
+        assert!(
+            input.bytes().all(|byte| byte.is_ascii_digit()),
+            "not a number"
+        );
+    }
+
+    // Produces `bench_short` and `bench_long`, alongside the test of every case
+    test_gen! {
+        #[generate(tests, benches)]
+        fn validate => {
+            short: {
+                ("1")
+            },
+            long: {
+                ("123456")
+            },
+            invalid: {
+                #[should_panic]
+                ("one")
+            },
+        }
+    }
+
+    fn encode(value: &u32) -> [u8; 4] {
+        value.to_le_bytes()
+    }
+
+    fn decode(encoded: &[u8; 4]) -> u32 {
+        u32::from_le_bytes(*encoded)
+    }
+
+    // Round-trip benchmarks call the encoding function with a reference to their value,
+    // as their tests do
+    test_gen! {
+        #[generate(tests, benches)]
+        roundtrip fn encode, fn decode => {
+            zero: {
+                (0)
+            },
+            max: {
+                (u32::MAX)
+            },
+        }
+    }
+}
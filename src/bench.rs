@@ -0,0 +1,269 @@
+//! Benchmarks of the cases of a block, produced alongside, or instead of, their tests,
+//! and performance budgets, checked by the tests themselves.
+//!
+//! Benchmarks are nightly `#[bench]` functions, or with `#[generate(benches = criterion)]`,
+//! registrations of `criterion` benchmarks, grouped by the helper function.
+use crate::take_attr;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Error, Ident, Lit, MetaNameValue, Path, Result, Token,
+};
+
+/// What a block produces for its cases, specified by `#[generate(tests, benches)]`.
+///
+/// Blocks only produce tests, unless specified otherwise.
+pub(crate) struct Generate {
+    pub(crate) tests: bool,
+    pub(crate) benches: Option<Backend>,
+}
+
+/// The framework benchmarks are produced for,
+/// specified by `benches = criterion`, or nightly `#[bench]` functions by default.
+#[derive(Clone, Copy)]
+pub(crate) enum Backend {
+    Nightly,
+    Criterion,
+}
+
+/// A type representing a kind of output within the `generate` attribute,
+/// and optionally, the backend it's produced for (e.g. `benches = criterion`).
+struct Kind {
+    name: Ident,
+    backend: Option<(Token![=], Ident)>,
+}
+
+impl Parse for Kind {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.parse()?;
+        let backend = if input.peek(Token![=]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+
+        Ok(Self { name, backend })
+    }
+}
+
+impl Generate {
+    /// Removes the `generate` attribute from the attributes of the block, if any.
+    pub(crate) fn take(attrs: &mut Vec<Attribute>) -> Result<Self> {
+        let attr = match take_attr(attrs, "generate") {
+            Some(attr) => attr,
+            None => {
+                return Ok(Self {
+                    tests: true,
+                    benches: None,
+                })
+            }
+        };
+
+        let mut generate = Self {
+            tests: false,
+            benches: None,
+        };
+
+        for Kind { name, backend } in
+            attr.parse_args_with(Punctuated::<Kind, Token![,]>::parse_terminated)?
+        {
+            match (name.to_string().as_str(), backend) {
+                ("tests", None) => generate.tests = true,
+                ("benches", None) => generate.benches = Some(Backend::Nightly),
+                ("benches", Some((_, backend))) if backend == "criterion" => {
+                    generate.benches = Some(Backend::Criterion)
+                }
+                ("benches", Some((_, backend))) => {
+                    return Err(Error::new_spanned(backend, "expected `criterion`"))
+                }
+                ("tests", Some((eq, _))) => {
+                    return Err(Error::new_spanned(
+                        eq,
+                        "only benchmarks can specify a backend, e.g. `benches = criterion`",
+                    ))
+                }
+                _ => return Err(Error::new_spanned(name, "expected `tests` or `benches`")),
+            }
+        }
+
+        if !generate.tests && generate.benches.is_none() {
+            return Err(Error::new_spanned(
+                attr,
+                "expected `tests`, `benches`, or both, e.g. `#[generate(tests, benches)]`",
+            ));
+        }
+
+        Ok(generate)
+    }
+}
+
+/// The benchmark of a case, calling the helper function with the arguments of the case.
+pub(crate) struct Bench {
+    cfgs: Vec<Attribute>,
+    name: Ident,
+    call: TokenStream2,
+}
+
+impl Bench {
+    /// Produces the benchmark of the case `name`.
+    ///
+    /// Only `cfg` attributes of `attrs` are applied to the benchmark,
+    /// as the other attributes of the case apply to its test.
+    pub(crate) fn new<'a>(
+        name: &Ident,
+        attrs: impl Iterator<Item = &'a Attribute>,
+        call: TokenStream2,
+    ) -> Self {
+        Self {
+            cfgs: attrs
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .cloned()
+                .collect(),
+            name: name.clone(),
+            call,
+        }
+    }
+}
+
+/// Produces the benchmarks of the block for `helper`, with the `cfg` attributes of `attrs`.
+///
+/// Nightly benchmarks are named after their case, prefixed by `bench_`,
+/// as they may be produced alongside a test of the same name. Criterion benchmarks
+/// are instead registered by a function named after the helper function, suffixed by `_benches`
+/// (e.g. `parse_benches`), which can be passed to `criterion_group!`.
+pub(crate) fn emit(
+    backend: Backend,
+    helper: &Path,
+    attrs: &[Attribute],
+    benches: Vec<Bench>,
+) -> TokenStream2 {
+    let cfgs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+
+    if let Backend::Nightly = backend {
+        let benches = benches.into_iter().map(|Bench { cfgs, name, call }| {
+            let name = format_ident!("bench_{}", name.unraw(), span = name.span());
+
+            quote! {
+                #(#cfgs)*
+                #[bench]
+                fn #name(__test_gen_bencher: &mut ::test::Bencher) {
+                    __test_gen_bencher.iter(|| #call);
+                }
+            }
+        });
+
+        return quote!(#(#benches)*);
+    }
+
+    let last = &helper
+        .segments
+        .last()
+        .expect("paths have at least one segment")
+        .ident;
+    let group = format_ident!("{}_benches", last.unraw(), span = last.span());
+    let helper = helper.to_token_stream().to_string().replace(' ', "");
+    let benches = benches.into_iter().map(|Bench { cfgs, name, call }| {
+        let name = name.unraw().to_string();
+
+        quote! {
+            #(#cfgs)*
+            __test_gen_group.bench_function(#name, |__test_gen_bencher| {
+                __test_gen_bencher.iter(|| #call)
+            });
+        }
+    });
+
+    quote! {
+        #(#cfgs)*
+        #[allow(dead_code)]
+        fn #group(__test_gen_criterion: &mut ::criterion::Criterion) {
+            let mut __test_gen_group = __test_gen_criterion.benchmark_group(#helper);
+            #(#benches)*
+            __test_gen_group.finish();
+        }
+    }
+}
+
+/// The performance budget of a case, specified by `#[budget(ms = 5, iterations = 1000)]`.
+///
+/// The budget is specified in either milliseconds (`ms`) or microseconds (`us`),
+/// and the helper function is called 100 times, unless specified otherwise.
+#[derive(Clone, Copy)]
+pub(crate) struct Budget {
+    nanos: u64,
+    iterations: usize,
+}
+
+impl Budget {
+    /// Parses the budget of a `budget` attribute.
+    pub(crate) fn parse(attr: &Attribute) -> Result<Self> {
+        let error = || {
+            Error::new_spanned(
+                attr,
+                "expected a budget in `ms` or `us`, e.g. `#[budget(ms = 5, iterations = 1000)]`",
+            )
+        };
+        let mut nanos = None;
+        let mut iterations = None;
+
+        for MetaNameValue { path, lit, .. } in
+            attr.parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?
+        {
+            let value = match &lit {
+                Lit::Int(int) => int.base10_parse::<u64>()?,
+                lit => return Err(Error::new_spanned(lit, "expected an integer")),
+            };
+            let (slot, value) = if path.is_ident("ms") {
+                (&mut nanos, value.checked_mul(1_000_000))
+            } else if path.is_ident("us") {
+                (&mut nanos, value.checked_mul(1_000))
+            } else if path.is_ident("iterations") {
+                (&mut iterations, Some(value))
+            } else {
+                return Err(Error::new_spanned(
+                    path,
+                    "expected `ms`, `us` or `iterations`",
+                ));
+            };
+
+            if slot.is_some() {
+                return Err(Error::new_spanned(path, "budget specified more than once"));
+            }
+
+            *slot = Some(value.ok_or_else(|| Error::new_spanned(&lit, "budget is too large"))?);
+        }
+
+        let iterations = match iterations {
+            Some(0) => return Err(Error::new_spanned(attr, "expected at least one iteration")),
+            Some(iterations) => {
+                usize::try_from(iterations).map_err(|err| Error::new_spanned(attr, err))?
+            }
+            None => 100,
+        };
+
+        Ok(Self {
+            nanos: nanos.ok_or_else(error)?,
+            iterations,
+        })
+    }
+
+    /// Produces the check of the case `name`, timing `call` repeatedly,
+    /// with its output passed through `black_box`, so it isn't optimised away.
+    pub(crate) fn check(self, name: &str, call: TokenStream2) -> TokenStream2 {
+        let Self { nanos, iterations } = self;
+
+        quote! {
+            __test_gen::budget::check(
+                #name,
+                ::std::time::Duration::from_nanos(#nanos),
+                #iterations,
+                || {
+                    let _ = __test_gen::budget::black_box(#call);
+                },
+            );
+        }
+    }
+}
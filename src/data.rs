@@ -0,0 +1,1001 @@
+//! Loading of test cases from data files, read while `test_gen` is being expanded.
+//!
+//! Data files are located relative to the `CARGO_MANIFEST_DIR` of the crate being compiled,
+//! and any errors encountered whilst reading them, report the file, line and column at fault.
+use crate::{FnArgs, TestCase};
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+mod glob;
+use syn::{
+    ext::IdentExt, punctuated::Punctuated, token::Paren, Error, Expr, Ident, LitStr, Path, Result,
+};
+
+/// A data file, read from the path specified by a case source.
+pub(crate) struct DataFile {
+    /// The literal specifying the path, used as the span of any errors.
+    lit: LitStr,
+    /// The absolute path of the file.
+    path: PathBuf,
+    /// The contents of the file.
+    pub(crate) contents: String,
+}
+
+impl DataFile {
+    /// Reads the file at the path specified by `lit`,
+    /// relative to the manifest directory of the crate being compiled.
+    pub(crate) fn read(lit: &LitStr) -> Result<Self> {
+        let path = manifest_dir(lit)?.join(lit.value());
+
+        fs::read_to_string(&path)
+            .map_err(|err| {
+                Error::new(
+                    lit.span(),
+                    format!("couldn't read `{}`: {}", lit.value(), err),
+                )
+            })
+            .map(|contents| Self {
+                lit: lit.clone(),
+                path,
+                contents,
+            })
+    }
+
+    /// Produces an error, reporting the `line` and `column` of the file at fault.
+    pub(crate) fn error(
+        &self,
+        line: usize,
+        column: usize,
+        message: impl std::fmt::Display,
+    ) -> Error {
+        Error::new(
+            self.lit.span(),
+            format!("{}:{}:{}: {}", self.lit.value(), line, column, message),
+        )
+    }
+
+    /// Produces an item, which causes the file to be tracked by the compiler,
+    /// so changes to it cause the crate to be rebuilt.
+    ///
+    /// Procedural macros don't have a stable means of tracking the files they read,
+    /// but files included by `include_bytes!` are tracked by the compiler, and the unused
+    /// constant is optimised away.
+    pub(crate) fn tracker(&self) -> TokenStream2 {
+        track(&self.path)
+    }
+}
+
+/// Produces the manifest directory of the crate being compiled,
+/// reporting an error spanning `lit` if it isn't available.
+pub(crate) fn manifest_dir(lit: &LitStr) -> Result<PathBuf> {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            Error::new(
+                lit.span(),
+                "`CARGO_MANIFEST_DIR` isn't set, so data files can't be located",
+            )
+        })
+}
+
+/// Produces an expression for the absolute path of `path`,
+/// relative to the manifest directory at the time the expression is compiled.
+pub(crate) fn manifest_path(path: &str, span: Span) -> TokenStream2 {
+    let path = LitStr::new(&format!("/{}", path), span);
+
+    quote!(::std::concat!(::std::env!("CARGO_MANIFEST_DIR"), #path))
+}
+
+/// Produces an item, which causes the file at `path` to be tracked by the compiler.
+pub(crate) fn track(path: &std::path::Path) -> TokenStream2 {
+    let path = path.to_string_lossy();
+
+    quote! {
+        const _: &[u8] = include_bytes!(#path);
+    }
+}
+
+/// Produces a valid identifier from `name`, for naming a case produced from a data file.
+///
+/// Any characters which aren't valid within an identifier are replaced by underscores,
+/// and names which can't begin an identifier are prefixed with `case_`.
+/// Non-ASCII characters valid within an identifier (e.g. `é` or `日`) are kept as they are.
+pub(crate) fn case_name(name: &str, span: Span) -> Option<Ident> {
+    let mut ident = String::with_capacity(name.len());
+
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        // Whether non-ASCII characters are valid within an identifier is left to the parser,
+        // rather than duplicating Unicode's tables of identifier characters.
+        if c.is_ascii_alphanumeric()
+            || (!c.is_ascii() && syn::parse_str::<Ident>(&format!("a{}", c)).is_ok())
+        {
+            ident.push(c);
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+
+    while ident.ends_with('_') {
+        ident.pop();
+    }
+
+    if ident.is_empty() {
+        return None;
+    }
+
+    // Digits, and non-ASCII characters such as combining marks, can't begin an identifier.
+    if ident.starts_with(|c: char| syn::parse_str::<Ident>(&c.to_string()).is_err()) {
+        ident.insert_str(0, "case_");
+    }
+
+    // Keywords can't be used as test names as they are,
+    // but can as raw identifiers.
+    Some(
+        syn::parse_str::<Ident>(&ident)
+            .map(|_| Ident::new(&ident, span))
+            .unwrap_or_else(|_| Ident::new_raw(&ident, span)),
+    )
+}
+
+/// Sets the span of every token within `tokens`.
+///
+/// Tokens parsed from strings are otherwise spanned to the macro's call site,
+/// rather than the source they were specified by.
+pub(crate) fn respan(tokens: TokenStream2, span: Span) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = &tt {
+                let mut respanned =
+                    proc_macro2::Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                tt = TokenTree::Group(respanned);
+            } else {
+                tt.set_span(span);
+            }
+
+            tt
+        })
+        .collect()
+}
+
+/// A value read from a data file, alongside its position within the file.
+pub(crate) struct Field {
+    pub(crate) value: String,
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// Parses delimited records from `file` (e.g. CSV or TSV).
+///
+/// Fields may be quoted, in which case they can contain delimiters and newlines,
+/// with quotes being escaped by doubling them. Empty lines are skipped.
+fn parse_records(file: &DataFile, delimiter: char) -> Result<Vec<Vec<Field>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut chars = file.contents.chars().peekable();
+    let (mut line, mut column) = (1, 1);
+
+    loop {
+        let (start_line, start_column) = (line, column);
+        let mut value = String::new();
+        let quoted = chars.peek() == Some(&'"');
+
+        if quoted {
+            chars.next();
+            column += 1;
+
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        column += 2;
+                        value.push('"');
+                    }
+                    Some('"') => {
+                        column += 1;
+                        break;
+                    }
+                    Some('\n') => {
+                        line += 1;
+                        column = 1;
+                        value.push('\n');
+                    }
+                    Some(c) => {
+                        column += 1;
+                        value.push(c);
+                    }
+                    None => {
+                        return Err(file.error(
+                            start_line,
+                            start_column,
+                            "unterminated quoted field",
+                        ))
+                    }
+                }
+            }
+        }
+
+        // Consumes the remainder of the field, up to the next delimiter or line break.
+        while let Some(&c) = chars.peek() {
+            if c == delimiter || c == '\n' || (c == '\r' && !quoted) {
+                break;
+            }
+
+            if quoted {
+                return Err(file.error(line, column, "unexpected character after quoted field"));
+            }
+
+            chars.next();
+            column += 1;
+            value.push(c);
+        }
+
+        record.push(Field {
+            value,
+            line: start_line,
+            column: start_column,
+        });
+
+        match chars.next() {
+            Some(c) if c == delimiter => column += 1,
+            Some('\r') if chars.peek() == Some(&'\n') || chars.peek().is_none() => {
+                chars.next();
+                line += 1;
+                column = 1;
+                records.push(std::mem::take(&mut record));
+            }
+            Some(_) => {
+                line += 1;
+                column = 1;
+                records.push(std::mem::take(&mut record));
+            }
+            None => {
+                records.push(record);
+                break;
+            }
+        }
+    }
+
+    // Empty lines are parsed as a record containing a single empty field.
+    records.retain(|record| !matches!(&record[..], [field] if field.value.is_empty()));
+
+    Ok(records)
+}
+
+/// The type of a column, which determines how its values are converted into arguments.
+enum ColumnType {
+    /// Values are parsed as Rust expressions.
+    Expr,
+    /// Values are taken as they are, as string literals.
+    Str,
+    /// Values are taken as they are, as owned strings.
+    String,
+    /// Values are parsed as a literal of the named primitive type.
+    Primitive(String),
+}
+
+impl ColumnType {
+    /// Parses the type annotation of a column header.
+    fn parse(file: &DataFile, field: &Field, annotation: Option<&str>) -> Result<Self> {
+        let annotation = match annotation {
+            Some(annotation) => annotation.trim(),
+            None => return Ok(Self::Expr),
+        };
+
+        match annotation {
+            "str" => Ok(Self::Str),
+            "String" => Ok(Self::String),
+            "bool" | "char" | "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16"
+            | "u32" | "u64" | "u128" | "usize" | "f32" | "f64" => {
+                Ok(Self::Primitive(annotation.to_owned()))
+            }
+            _ => Err(file.error(
+                field.line,
+                field.column,
+                format!(
+                    "unsupported column type `{}`, expected `str`, `String`, or a primitive type",
+                    annotation
+                ),
+            )),
+        }
+    }
+
+    /// Converts `field` into an argument expression.
+    fn to_expr(&self, file: &DataFile, field: &Field) -> Result<Expr> {
+        let span = file.lit.span();
+        let value = &field.value;
+        let invalid = |ty: &str| {
+            file.error(
+                field.line,
+                field.column,
+                format!("`{}` isn't a valid `{}`", value, ty),
+            )
+        };
+
+        let tokens = match self {
+            Self::Expr => {
+                return syn::parse_str::<Expr>(value)
+                    .map_err(|err| file.error(field.line, field.column, err))
+                    .and_then(|expr| syn::parse2(respan(quote!(#expr), span)))
+            }
+            Self::Str => {
+                let lit = LitStr::new(value, span);
+                quote!(#lit)
+            }
+            Self::String => {
+                let lit = LitStr::new(value, span);
+                quote!(::std::string::String::from(#lit))
+            }
+            Self::Primitive(ty) if ty == "bool" => match value.trim() {
+                "true" => quote!(true),
+                "false" => quote!(false),
+                _ => return Err(invalid(ty)),
+            },
+            Self::Primitive(ty) if ty == "char" => {
+                let mut chars = value.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => quote!(#c),
+                    _ => return Err(invalid(ty)),
+                }
+            }
+            Self::Primitive(ty) => {
+                // Numeric literals are validated by parsing them with a suffix of their type,
+                // which is also how they are passed to the helper function.
+                let value = value.trim();
+                let (negative, digits) = match value.strip_prefix('-') {
+                    Some(digits) => (true, digits),
+                    None => (false, value),
+                };
+                let lit = syn::parse_str::<syn::Lit>(&format!("{}{}", digits, ty))
+                    .ok()
+                    .filter(|lit| match lit {
+                        // Whole numbers with a float suffix (e.g. `1f64`) are parsed as integers.
+                        syn::Lit::Int(int) => {
+                            int.suffix() == ty && (ty == "f32" || ty == "f64" || fits_in(value, ty))
+                        }
+                        syn::Lit::Float(float) => float.suffix() == ty,
+                        _ => false,
+                    })
+                    .ok_or_else(|| invalid(ty))?;
+                let tokens = if negative {
+                    quote!(-#lit)
+                } else {
+                    quote!(#lit)
+                };
+
+                respan(tokens, span)
+            }
+        };
+
+        syn::parse2(tokens)
+    }
+}
+
+/// Identifies whether the integer `value` is within the range of the integer type `ty`.
+///
+/// As the width of `usize` and `isize` depends on the target the tests are compiled for,
+/// which is unknown to the macro, they're treated as 32-bit, so values are valid on any target.
+pub(crate) fn fits_in(value: &str, ty: &str) -> bool {
+    match ty {
+        "i8" => value.parse::<i8>().is_ok(),
+        "i16" => value.parse::<i16>().is_ok(),
+        "i32" => value.parse::<i32>().is_ok(),
+        "i64" => value.parse::<i64>().is_ok(),
+        "i128" => value.parse::<i128>().is_ok(),
+        "isize" => value.parse::<i32>().is_ok(),
+        "u8" => value.parse::<u8>().is_ok(),
+        "u16" => value.parse::<u16>().is_ok(),
+        "u32" => value.parse::<u32>().is_ok(),
+        "u64" => value.parse::<u64>().is_ok(),
+        "u128" => value.parse::<u128>().is_ok(),
+        "usize" => value.parse::<u32>().is_ok(),
+        _ => false,
+    }
+}
+
+/// Produces a test case for every record of a delimited data file (e.g. CSV or TSV).
+///
+/// The first record of the file is treated as a header, which must include a `name` column,
+/// naming each case. The remaining columns are passed to the helper function in order,
+/// and may be annotated with a type (e.g. `input: str`), otherwise being parsed as expressions.
+pub(crate) fn delimited_cases(file: &DataFile, delimiter: char) -> Result<Vec<TestCase>> {
+    let mut records = parse_records(file, delimiter)?.into_iter();
+    let header = records
+        .next()
+        .ok_or_else(|| file.error(1, 1, "expected a header row"))?;
+
+    let mut name_column = None;
+    let mut columns = Vec::with_capacity(header.len());
+
+    for (index, field) in header.iter().enumerate() {
+        let mut parts = field.value.splitn(2, ':');
+        let column = parts.next().unwrap_or_default().trim();
+
+        if column == "name" {
+            if name_column.replace(index).is_some() {
+                return Err(file.error(field.line, field.column, "duplicate `name` column"));
+            }
+        } else {
+            columns.push((index, ColumnType::parse(file, field, parts.next())?));
+        }
+    }
+
+    let name_column = name_column.ok_or_else(|| file.error(1, 1, "expected a `name` column"))?;
+    let span = file.lit.span();
+
+    records
+        .map(|record| {
+            let first = &record[0];
+
+            if record.len() != header.len() {
+                return Err(file.error(
+                    first.line,
+                    first.column,
+                    format!("expected {} fields, found {}", header.len(), record.len()),
+                ));
+            }
+
+            let name = &record[name_column];
+            let fn_name = case_name(&name.value, span)
+                .ok_or_else(|| file.error(name.line, name.column, "expected a test case name"))?;
+            let args = columns
+                .iter()
+                .map(|(index, ty)| ty.to_expr(file, &record[*index]))
+                .collect::<Result<Punctuated<_, _>>>()?;
+
+            Ok(TestCase::new(
+                fn_name,
+                FnArgs {
+                    parens: Paren(span),
+                    args,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// A value read from a structured data file (e.g. JSON or TOML).
+///
+/// Values of either format are converted into this type,
+/// so they can share the same conversion into test cases.
+#[cfg_attr(not(any(feature = "json", feature = "toml")), allow(dead_code))]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    Str(String),
+    Array(Vec<Value>),
+    Table(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parses `file` as JSON.
+    #[cfg(feature = "json")]
+    pub(crate) fn from_json(file: &DataFile) -> Result<Self> {
+        fn convert(value: serde_json::Value) -> Value {
+            use serde_json::Value as Json;
+
+            match value {
+                Json::Null => Value::Null,
+                Json::Bool(value) => Value::Bool(value),
+                Json::Number(value) => value
+                    .as_i64()
+                    .map(i128::from)
+                    .or_else(|| value.as_u64().map(i128::from))
+                    .map_or_else(
+                        || Value::Float(value.as_f64().unwrap_or_default()),
+                        Value::Int,
+                    ),
+                Json::String(value) => Value::Str(value),
+                Json::Array(values) => Value::Array(values.into_iter().map(convert).collect()),
+                Json::Object(values) => Value::Table(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key, convert(value)))
+                        .collect(),
+                ),
+            }
+        }
+
+        serde_json::from_str(&file.contents)
+            .map(convert)
+            .map_err(|err| file.error(err.line(), err.column(), err))
+    }
+
+    /// Parses `file` as JSON, which requires the `json` feature.
+    #[cfg(not(feature = "json"))]
+    pub(crate) fn from_json(file: &DataFile) -> Result<Self> {
+        Err(Error::new(
+            file.lit.span(),
+            "loading cases from JSON files requires the `json` feature of `test_gen`",
+        ))
+    }
+
+    /// Parses `file` as TOML.
+    #[cfg(feature = "toml")]
+    pub(crate) fn from_toml(file: &DataFile) -> Result<Self> {
+        fn convert(value: toml::Value) -> Value {
+            use toml::Value as Toml;
+
+            match value {
+                Toml::Boolean(value) => Value::Bool(value),
+                Toml::Integer(value) => Value::Int(value.into()),
+                Toml::Float(value) => Value::Float(value),
+                Toml::String(value) => Value::Str(value),
+                Toml::Datetime(value) => Value::Str(value.to_string()),
+                Toml::Array(values) => Value::Array(values.into_iter().map(convert).collect()),
+                Toml::Table(values) => Value::Table(
+                    values
+                        .into_iter()
+                        .map(|(key, value)| (key, convert(value)))
+                        .collect(),
+                ),
+            }
+        }
+
+        // Positions reported by `toml` are zero-based,
+        // unlike the positions reported for every other format.
+        toml::from_str(&file.contents).map(convert).map_err(|err| {
+            let (line, column) = err.line_col().unwrap_or_default();
+
+            file.error(line + 1, column + 1, err)
+        })
+    }
+
+    /// Parses `file` as TOML, which requires the `toml` feature.
+    #[cfg(not(feature = "toml"))]
+    pub(crate) fn from_toml(file: &DataFile) -> Result<Self> {
+        Err(Error::new(
+            file.lit.span(),
+            "loading cases from TOML files requires the `toml` feature of `test_gen`",
+        ))
+    }
+
+    /// Converts the value into an argument expression.
+    ///
+    /// Tables are converted into struct literals of `ty`, so can only be converted
+    /// where a type is specified, and only at the top level of a case.
+    /// Nulls can't be converted, as no other value is converted into an `Option`.
+    fn to_expr(&self, ty: Option<&Path>, span: Span) -> std::result::Result<Expr, String> {
+        let tokens = match self {
+            Self::Null => {
+                return Err(String::from(
+                    "`null` can't be converted, as other values aren't wrapped in `Some`",
+                ))
+            }
+            Self::Bool(value) => quote!(#value),
+            Self::Int(value) => {
+                let lit = Literal::u128_unsuffixed(value.unsigned_abs());
+
+                if *value < 0 {
+                    quote!(-#lit)
+                } else {
+                    quote!(#lit)
+                }
+            }
+            Self::Float(value) if !value.is_finite() => {
+                return Err(format!("`{}` can't be represented as a literal", value))
+            }
+            Self::Float(value) => {
+                let lit = Literal::f64_unsuffixed(value.abs());
+
+                if value.is_sign_negative() {
+                    quote!(-#lit)
+                } else {
+                    quote!(#lit)
+                }
+            }
+            Self::Str(value) => {
+                let lit = LitStr::new(value, span);
+                quote!(#lit)
+            }
+            Self::Array(values) => {
+                let values = values
+                    .iter()
+                    .map(|value| value.to_expr(None, span))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+                quote!(::std::vec![#(#values),*])
+            }
+            Self::Table(values) => {
+                let ty = ty.ok_or_else(|| {
+                    String::from("tables can only be converted into a struct literal of the type specified by `as`")
+                })?;
+                let fields = values
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = syn::parse_str::<Ident>(key)
+                            .map(|_| Ident::new(key, span))
+                            .or_else(|_| syn::parse_str::<Ident>(&format!("r#{}", key)))
+                            .map_err(|_| format!("`{}` isn't a valid field name", key))?;
+                        let value = value.to_expr(None, span)?;
+
+                        Ok(quote!(#key: #value))
+                    })
+                    .collect::<std::result::Result<Vec<_>, String>>()?;
+
+                quote!(#ty { #(#fields),* })
+            }
+        };
+
+        syn::parse2(respan(tokens, span)).map_err(|err| err.to_string())
+    }
+}
+
+/// Produces a test case for every entry of the top-level table of a structured data file.
+///
+/// Keys name each case, whilst arrays are passed to the helper function as its arguments,
+/// and any other value is passed as the only argument. Tables are passed as a struct literal
+/// of `ty`, where it's specified.
+pub(crate) fn structured_cases(
+    file: &DataFile,
+    value: Value,
+    ty: Option<&Path>,
+) -> Result<Vec<TestCase>> {
+    let span = file.lit.span();
+    let cases = match value {
+        Value::Table(cases) => cases,
+        _ => return Err(file.error(1, 1, "expected a table of cases")),
+    };
+
+    let mut names = HashMap::new();
+
+    cases
+        .into_iter()
+        .map(|(name, value)| {
+            let error = |message| {
+                Error::new(
+                    span,
+                    format!("{}: case `{}`: {}", file.lit.value(), name, message),
+                )
+            };
+            let fn_name = case_name(&name, span)
+                .ok_or_else(|| error(String::from("expected a test case name")))?;
+
+            if let Some(other) = names.insert(fn_name.to_string(), name.clone()) {
+                return Err(error(format!(
+                    "`{}` would also produce a case named `{}`",
+                    other, fn_name
+                )));
+            }
+
+            let args = match value {
+                Value::Array(values) => values,
+                value => vec![value],
+            };
+            let args = args
+                .iter()
+                .map(|value| value.to_expr(ty, span).map_err(error))
+                .collect::<Result<Punctuated<_, _>>>()?;
+
+            Ok(TestCase::new(
+                fn_name,
+                FnArgs {
+                    parens: Paren(span),
+                    args,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Produces a test case for every line of a regressions file,
+/// with each line specifying the arguments of a case within parentheses (e.g. `(vec![0, 0])`).
+///
+/// Cases are named after the stem of the file, and numbered by the line they're on
+/// (e.g. `insertion_sort_0003`), so blocks can replay several files, and editing, commenting out
+/// or removing an entry doesn't rename the others. Empty lines and lines only containing
+/// comments are skipped.
+pub(crate) fn regression_cases(file: &DataFile) -> Result<Vec<TestCase>> {
+    let span = file.lit.span();
+    let stem = file
+        .path
+        .file_stem()
+        .and_then(|stem| case_name(&stem.to_string_lossy(), span))
+        .map_or_else(
+            || String::from("regression"),
+            |stem| stem.unraw().to_string(),
+        );
+    let mut cases = Vec::new();
+
+    for (index, line) in file.contents.lines().enumerate() {
+        let tokens = line
+            .parse::<TokenStream2>()
+            .map_err(|err| file.error(index + 1, 1, err))?;
+
+        if tokens.is_empty() {
+            continue;
+        }
+
+        let args = syn::parse2::<FnArgs>(respan(tokens, span))
+            .map_err(|err| file.error(index + 1, 1, err))?;
+        let fn_name = Ident::new(&format!("{}_{:04}", stem, index + 1), span);
+
+        cases.push(TestCase::new(fn_name, args));
+    }
+
+    Ok(cases)
+}
+
+/// How the files matched by a glob pattern are passed to the helper function.
+pub(crate) enum GlobMode {
+    /// The path of the file, as a `&'static str`.
+    Path,
+    /// The contents of the file, included as a `&'static str`.
+    Str,
+    /// The contents of the file, included as a `&'static [u8; N]`.
+    Bytes,
+}
+
+/// A file matched by a glob pattern.
+pub(crate) struct GlobFile {
+    /// The name of the case produced for the file.
+    pub(crate) fn_name: Ident,
+    /// The path of the file, relative to the manifest directory.
+    pub(crate) path: String,
+}
+
+impl GlobFile {
+    /// Produces an expression for the absolute path of the file,
+    /// relative to the manifest directory at the time the expression is compiled.
+    pub(crate) fn path_expr(&self, span: Span) -> TokenStream2 {
+        manifest_path(&self.path, span)
+    }
+}
+
+/// Produces every file matching the glob pattern `lit`, sorted by path,
+/// alongside the name of the case for each file.
+///
+/// Case names are derived from the path of each file relative to the pattern's
+/// leading directories, without its extension (e.g. `fixtures/**/*.txt` matching
+/// `fixtures/nested/empty.txt` produces `nested_empty`).
+pub(crate) fn glob_files(lit: &LitStr) -> Result<Vec<GlobFile>> {
+    let span = lit.span();
+    let value = lit.value();
+    let pattern = glob::Pattern::new(&value).map_err(|err| Error::new(span, err))?;
+    let files = pattern.files(&manifest_dir(lit)?).map_err(|err| {
+        Error::new(
+            span,
+            format!("couldn't read files matching `{}`: {}", value, err),
+        )
+    })?;
+
+    if files.is_empty() {
+        return Err(Error::new(span, format!("no files match `{}`", value)));
+    }
+
+    let mut names = HashMap::new();
+
+    files
+        .into_iter()
+        .map(|file| {
+            let relative = file
+                .iter()
+                .map(|component| component.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let fn_name = file
+                .with_extension("")
+                .to_str()
+                .and_then(|name| case_name(name, span))
+                .ok_or_else(|| {
+                    Error::new(
+                        span,
+                        format!("couldn't derive a case name for `{}`", relative),
+                    )
+                })?;
+
+            if let Some(other) = names.insert(fn_name.to_string(), relative.clone()) {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`{}` and `{}` would both produce a case named `{}`",
+                        other, relative, fn_name
+                    ),
+                ));
+            }
+
+            let path = pattern
+                .base
+                .iter()
+                .copied()
+                .chain(Some(&*relative))
+                .collect::<Vec<_>>()
+                .join("/");
+
+            Ok(GlobFile { fn_name, path })
+        })
+        .collect()
+}
+
+impl GlobFile {
+    /// Produces the argument passing the file to the helper function,
+    /// tracking the file within `tracked` where it isn't included.
+    fn arg(
+        &self,
+        mode: &GlobMode,
+        root: &std::path::Path,
+        span: Span,
+        tracked: &mut TokenStream2,
+    ) -> Result<FnArgs> {
+        let path = self.path_expr(span);
+        let arg = match mode {
+            GlobMode::Path => {
+                tracked.extend(track(&root.join(&self.path)));
+                path
+            }
+            GlobMode::Str => quote!(::std::include_str!(#path)),
+            GlobMode::Bytes => quote!(::std::include_bytes!(#path)),
+        };
+
+        syn::parse2(respan(arg, span)).map(|arg| FnArgs {
+            parens: Paren(span),
+            args: std::iter::once::<Expr>(arg).collect(),
+        })
+    }
+}
+
+/// Produces a test case for every file matching the glob pattern `lit`,
+/// passing either the path of the file, or its contents, to the helper function.
+///
+/// Included files are tracked by the compiler through their inclusion,
+/// whilst files passed by path are tracked separately.
+///
+/// Files added after the block is expanded can't be tracked, as only files can be,
+/// rather than the directories they're matched within, so they're left to a build script.
+pub(crate) fn glob_cases(lit: &LitStr, mode: GlobMode) -> Result<(Vec<TestCase>, TokenStream2)> {
+    let span = lit.span();
+    let root = manifest_dir(lit)?;
+    let mut tracked = TokenStream2::new();
+
+    let cases = glob_files(lit)?
+        .into_iter()
+        .map(|file| {
+            file.arg(&mode, &root, span, &mut tracked)
+                .map(|args| TestCase::new(file.fn_name, args))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((cases, tracked))
+}
+
+/// Produces a golden test case for every input file matching the glob pattern `lit`,
+/// comparing the output of the helper function with the file of the same name,
+/// with an `out` extension (e.g. `tests/golden/foo.in` is compared with `tests/golden/foo.out`).
+///
+/// Expected outputs are read when the tests are run, rather than being tracked,
+/// as blessing the tests rewrites them.
+pub(crate) fn golden_cases(lit: &LitStr, mode: GlobMode) -> Result<(Vec<TestCase>, TokenStream2)> {
+    let span = lit.span();
+    let root = manifest_dir(lit)?;
+    let mut tracked = TokenStream2::new();
+
+    let cases = glob_files(lit)?
+        .into_iter()
+        .map(|file| {
+            let expected = std::path::Path::new(&file.path).with_extension("out");
+            let expected = expected.to_string_lossy();
+
+            if expected == file.path {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "`{}` is an expected output, so can't also be an input",
+                        file.path
+                    ),
+                ));
+            }
+
+            let mut case = TestCase::new(
+                file.fn_name.clone(),
+                file.arg(&mode, &root, span, &mut tracked)?,
+            );
+
+            case.args
+                .attrs
+                .push(syn::parse_quote_spanned!(span=> #[golden(#expected)]));
+
+            Ok(case)
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((cases, tracked))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(contents: &str) -> Vec<Vec<String>> {
+        let file = DataFile {
+            lit: LitStr::new("test.csv", Span::call_site()),
+            path: PathBuf::from("test.csv"),
+            contents: contents.to_owned(),
+        };
+
+        parse_records(&file, ',')
+            .expect("records could not be parsed")
+            .into_iter()
+            .map(|record| record.into_iter().map(|field| field.value).collect())
+            .collect()
+    }
+
+    #[test]
+    fn records_parsing() {
+        assert_eq!(
+            parse("a,b\r\n\n\"c, \"\"d\"\"\",\"e\nf\"\n"),
+            [vec!["a", "b"], vec!["c, \"d\"", "e\nf"]]
+        );
+    }
+
+    #[test]
+    fn value_conversion() {
+        let ty: Path = syn::parse_quote!(Case);
+        let expr = |value: Value| {
+            value
+                .to_expr(Some(&ty), Span::call_site())
+                .map(|expr| quote!(#expr).to_string())
+        };
+
+        assert_eq!(expr(Value::Int(-3)).as_deref(), Ok("- 3"));
+        assert_eq!(
+            expr(Value::Array(vec![Value::Bool(true), Value::Int(1)])).as_deref(),
+            Ok(":: std :: vec ! [true , 1]")
+        );
+        assert!(expr(Value::Array(vec![Value::Null])).is_err());
+        assert_eq!(
+            expr(Value::Table(vec![(
+                String::from("type"),
+                Value::Str(String::from("a"))
+            )]))
+            .as_deref(),
+            Ok("Case { r#type : \"a\" }")
+        );
+        assert!(expr(Value::Float(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn structured_case_names() {
+        let file = DataFile {
+            lit: LitStr::new("test.json", Span::call_site()),
+            path: PathBuf::from("test.json"),
+            contents: String::new(),
+        };
+        let cases = |names: &[&str]| {
+            let cases = names
+                .iter()
+                .map(|name| (name.to_string(), Value::Int(1)))
+                .collect();
+
+            structured_cases(&file, Value::Table(cases), None)
+                .map(|cases| cases.len())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(cases(&["a-b", "a b c"]), Ok(2));
+        assert_eq!(
+            cases(&["a-b", "a_b"]),
+            Err(String::from(
+                "test.json: case `a_b`: `a-b` would also produce a case named `a_b`"
+            ))
+        );
+    }
+
+    #[test]
+    fn case_name_sanitising() {
+        let name = |name| case_name(name, Span::call_site()).map(|ident| ident.to_string());
+
+        assert_eq!(name("Two Words!").as_deref(), Some("two_words"));
+        assert_eq!(name("1st").as_deref(), Some("case_1st"));
+        assert_eq!(name("type").as_deref(), Some("r#type"));
+        assert_eq!(name(" - "), None);
+        assert_eq!(name("Café Crème").as_deref(), Some("café_crème"));
+        assert_eq!(name("日本").as_deref(), Some("日本"));
+        assert_eq!(name("x²").as_deref(), Some("x"));
+        assert_eq!(name("\u{301}e").as_deref(), Some("case_\u{301}e"));
+    }
+}
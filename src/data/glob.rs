@@ -0,0 +1,181 @@
+//! Matching of files against glob patterns (e.g. `tests/fixtures/**/*.txt`).
+//!
+//! Only the subset of glob syntax required for locating fixtures is supported:
+//! `*` and `?` match any characters within a path component,
+//! whilst a `**` component matches any number of directories.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A glob pattern, split into its path components.
+pub(crate) struct Pattern<'a> {
+    /// The leading components of the pattern, which don't contain any wildcards.
+    pub(crate) base: Vec<&'a str>,
+    /// The remaining components of the pattern.
+    components: Vec<&'a str>,
+}
+
+impl<'a> Pattern<'a> {
+    /// Splits `pattern` into its components, separating the leading components without wildcards.
+    pub(crate) fn new(pattern: &'a str) -> Result<Self, &'static str> {
+        if pattern.starts_with('/') {
+            return Err("patterns must be relative to the crate's manifest directory");
+        }
+
+        let mut components = pattern
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .peekable();
+        let mut base = Vec::new();
+
+        while let Some(component) = components.next_if(|component| !is_wild(component)) {
+            base.push(component);
+        }
+
+        let components = components.collect::<Vec<_>>();
+
+        if components.is_empty() {
+            return Err("patterns must contain a wildcard, to match any files");
+        }
+
+        Ok(Self { base, components })
+    }
+
+    /// Produces the paths of every file matching the pattern within `root`,
+    /// relative to the base directory of the pattern, and sorted.
+    pub(crate) fn files(&self, root: &Path) -> io::Result<Vec<PathBuf>> {
+        let base = self
+            .base
+            .iter()
+            .fold(root.to_path_buf(), |path, component| path.join(component));
+        let mut files = Vec::new();
+
+        if base.is_dir() {
+            walk(&base, PathBuf::new(), &self.components, &mut files)?;
+        }
+
+        files.sort();
+        files.dedup();
+
+        Ok(files)
+    }
+}
+
+/// Identifies whether a path component contains any wildcards.
+fn is_wild(component: &str) -> bool {
+    component.contains(['*', '?'])
+}
+
+/// Appends every file within `dir` matching `components` to `files`,
+/// `relative` being the path of `dir`, relative to the base directory of the pattern.
+fn walk(
+    dir: &Path,
+    relative: PathBuf,
+    components: &[&str],
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let (component, rest) = match components.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+
+    // `**` matches zero directories, as well as any number of them.
+    if *component == "**" {
+        walk(dir, relative.clone(), rest, files)?;
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = match name.to_str() {
+            Some(name) => name,
+            None => continue,
+        };
+        let path = relative.join(name);
+        let file_type = entry.file_type()?;
+
+        if *component == "**" {
+            if file_type.is_dir() && !name.starts_with('.') {
+                walk(&entry.path(), path, components, files)?;
+            }
+        } else if matches(component, name) {
+            if rest.is_empty() {
+                if file_type.is_file() {
+                    files.push(path);
+                }
+            } else if file_type.is_dir() {
+                walk(&entry.path(), path, rest, files)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Identifies whether `name` matches the pattern `component`.
+///
+/// Hidden files are only matched, where the pattern explicitly begins with a `.`.
+pub(crate) fn matches(component: &str, name: &str) -> bool {
+    if name.starts_with('.') && !component.starts_with('.') {
+        return false;
+    }
+
+    let pattern = component.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    // Standard backtracking matcher, where only the most recent `*` needs to be revisited.
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+            }
+            Some(&c) if c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn component_matching() {
+        assert!(matches("*.txt", "a.txt"));
+        assert!(matches("a?c*", "abc.rs"));
+        assert!(matches("*a*b", "xaxxb"));
+        assert!(!matches("*.txt", "a.txt.bak"));
+        assert!(!matches("*.txt", ".hidden.txt"));
+        assert!(matches(".*", ".hidden"));
+    }
+
+    #[test]
+    fn pattern_splitting() {
+        let pattern = Pattern::new("./tests/fixtures/**/*.txt").unwrap();
+
+        assert_eq!(pattern.base, ["tests", "fixtures"]);
+        assert_eq!(pattern.components, ["**", "*.txt"]);
+        assert!(Pattern::new("tests/fixtures").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Compile-fail cases, emitted as `compile_fail` doctests of a hidden item.
+//!
+//! Doctests are compiled as a separate crate, linked to the crate being tested,
+//! so `crate` within the case is replaced by the name of the crate, as it'd be referred to
+//! from outside of it. The item itself is only produced for `rustdoc`, with `cfg(doctest)`.
+//!
+//! Any other path which isn't absolute would fail to resolve within the doctest,
+//! satisfying the case whether or not the call itself compiles, so such paths are rejected.
+use proc_macro2::{Group, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::{Attribute, Error, Expr, Ident, Path, Result};
+
+/// Produces the hidden item documented by the doctest of the case `name`,
+/// which fails to compile `call`, with the error code specified by `attr`, if any.
+///
+/// Only `cfg` attributes of `attrs` are applied to the item,
+/// as the case isn't produced as a test.
+pub(crate) fn compile_fail<'a>(
+    attr: &Attribute,
+    attrs: impl Iterator<Item = &'a Attribute>,
+    name: &Ident,
+    call: TokenStream2,
+) -> Result<TokenStream2> {
+    let code = (!attr.tokens.is_empty())
+        .then(|| error_code(attr))
+        .transpose()?;
+    let lang = match code {
+        Some(code) => format!("compile_fail,{}", code),
+        None => String::from("compile_fail"),
+    };
+    check_paths(&syn::parse2(call.clone())?)?;
+
+    let cfgs = attrs.filter(|attr| attr.path.is_ident("cfg"));
+    let source = match std::env::var("CARGO_CRATE_NAME") {
+        Ok(krate) => external(call, &krate),
+        Err(_) => call,
+    };
+    let doc = format!("```{}\nlet _ = {};\n```", lang, source);
+
+    Ok(quote! {
+        #(#cfgs)*
+        #[cfg(doctest)]
+        #[doc = #doc]
+        #[doc(hidden)]
+        #[allow(non_camel_case_types, dead_code)]
+        struct #name;
+    })
+}
+
+/// Parses the error code of `#[compile_fail(E0277)]`.
+fn error_code(attr: &Attribute) -> Result<Ident> {
+    let error = || Error::new_spanned(attr, "expected an error code, e.g. `E0277`");
+    let code = attr.parse_args::<Ident>().map_err(|_| error())?;
+    let digits = code.to_string();
+    let digits = digits.strip_prefix('E').ok_or_else(error)?;
+
+    (digits.len() == 4 && digits.chars().all(|c| c.is_ascii_digit()))
+        .then_some(code)
+        .ok_or_else(error)
+}
+
+/// Checks every path within `expr` is qualified by `crate`, or absolute (e.g. `::std::vec::Vec`),
+/// so the doctest only fails to compile for the reason the case expects.
+///
+/// The paths of types (e.g. of casts) aren't checked, so primitive types can still be used,
+/// whilst expressions which may refer to items through paths that can't be checked,
+/// such as closures and blocks, are rejected.
+fn check_paths(expr: &Expr) -> Result<()> {
+    match expr {
+        Expr::Lit(_) => Ok(()),
+        Expr::Path(expr) => match &expr.qself {
+            Some(qself) => Err(Error::new_spanned(
+                &qself.ty,
+                "qualified paths can't be used within compile-fail cases",
+            )),
+            None => check_path(&expr.path),
+        },
+        Expr::Macro(expr) => check_path(&expr.mac.path),
+        Expr::Struct(expr) => {
+            check_path(&expr.path)?;
+            expr.fields
+                .iter()
+                .map(|field| &field.expr)
+                .chain(expr.rest.as_deref())
+                .try_for_each(check_paths)
+        }
+        Expr::Call(expr) => {
+            check_paths(&expr.func)?;
+            expr.args.iter().try_for_each(check_paths)
+        }
+        Expr::MethodCall(expr) => {
+            check_paths(&expr.receiver)?;
+            expr.args.iter().try_for_each(check_paths)
+        }
+        Expr::Tuple(expr) => expr.elems.iter().try_for_each(check_paths),
+        Expr::Array(expr) => expr.elems.iter().try_for_each(check_paths),
+        Expr::Repeat(expr) => check_paths(&expr.expr).and_then(|_| check_paths(&expr.len)),
+        Expr::Binary(expr) => check_paths(&expr.left).and_then(|_| check_paths(&expr.right)),
+        Expr::Index(expr) => check_paths(&expr.expr).and_then(|_| check_paths(&expr.index)),
+        Expr::Range(expr) => expr
+            .from
+            .iter()
+            .chain(&expr.to)
+            .try_for_each(|expr| check_paths(expr)),
+        Expr::Reference(expr) => check_paths(&expr.expr),
+        Expr::Unary(expr) => check_paths(&expr.expr),
+        Expr::Paren(expr) => check_paths(&expr.expr),
+        Expr::Group(expr) => check_paths(&expr.expr),
+        Expr::Field(expr) => check_paths(&expr.base),
+        Expr::Cast(expr) => check_paths(&expr.expr),
+        Expr::Try(expr) => check_paths(&expr.expr),
+        expr => Err(Error::new_spanned(
+            expr,
+            "compile-fail cases can only pass paths, literals, and calls and operations of them",
+        )),
+    }
+}
+
+/// Checks `path` is qualified by `crate`, or absolute.
+fn check_path(path: &Path) -> Result<()> {
+    let qualified = path.leading_colon.is_some()
+        || matches!(path.segments.first(), Some(segment) if segment.ident == "crate");
+
+    if qualified {
+        return Ok(());
+    }
+
+    Err(Error::new_spanned(
+        path,
+        "paths within compile-fail cases must be qualified by `crate::`, or absolute \
+        (e.g. `::std::string::String`), as the case is compiled as a separate crate",
+    ))
+}
+
+/// Replaces `crate` within `tokens` by the name of the crate `krate`.
+fn external(tokens: TokenStream2, krate: &str) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "crate" => {
+                TokenTree::Ident(Ident::new(krate, ident.span()))
+            }
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), external(group.stream(), krate));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced)
+            }
+            token => token,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::format_ident;
+    use syn::parse_quote;
+
+    #[test]
+    fn compile_fail_docs() {
+        let name = format_ident!("case");
+        let doc = |attr: Attribute| {
+            compile_fail(&attr, [].iter(), &name, quote!(crate::helper(1)))
+                .map(|item| item.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        assert!(doc(parse_quote!(#[compile_fail]))
+            .unwrap()
+            .contains(r#""```compile_fail\nlet _ = crate :: helper (1);\n```""#));
+        assert!(doc(parse_quote!(#[compile_fail(E0277)]))
+            .unwrap()
+            .contains("compile_fail,E0277"));
+        assert!(doc(parse_quote!(#[compile_fail(E277)])).is_err());
+        assert!(doc(parse_quote!(#[compile_fail("E0277")])).is_err());
+    }
+
+    #[test]
+    fn compile_fail_paths() {
+        let name = format_ident!("case");
+        let attr = parse_quote!(#[compile_fail]);
+        let doc = |call| compile_fail(&attr, [].iter(), &name, call).is_ok();
+
+        assert!(doc(quote!(crate::helper(crate::Value { x: 1 }, -1))));
+        assert!(doc(quote!(::krate::helper(&crate::new().open(), 1 as u8))));
+        assert!(doc(quote!(crate::helper(
+            ::std::vec![0; 2],
+            [crate::A, crate::B]
+        ))));
+        assert!(!doc(quote!(helper(1))));
+        assert!(!doc(quote!(crate::helper(Value { x: 1 }))));
+        assert!(!doc(quote!(crate::helper(String::new()))));
+        assert!(!doc(quote!(crate::helper(vec![1]))));
+        assert!(!doc(quote!(crate::helper(self::A))));
+        assert!(!doc(quote!(crate::helper(|| A))));
+    }
+
+    #[test]
+    fn external_crate_paths() {
+        assert_eq!(
+            external(quote!(crate::helper(crate::Value { x: 1 })), "krate").to_string(),
+            quote!(krate::helper(krate::Value { x: 1 })).to_string()
+        );
+    }
+}
@@ -0,0 +1,346 @@
+//! Expansion of generators, specified in place of an argument,
+//! into a case for every value they produce (e.g. the boundaries of an integer type,
+//! or the entries of an edge-case corpus).
+//!
+//! Cases are named after the case specifying the generator, suffixed by the name of each value
+//! (e.g. `len_max_minus_1`). Cases specifying multiple generators are expanded
+//! into a case for every combination of their values.
+use crate::{data, CaseBody, TestCase};
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_quote_spanned, spanned::Spanned, Error, Expr, ExprAssign, ExprCall,
+    ExprLit, ExprPath, ExprUnary, Lit, LitByteStr, LitInt, LitStr, Result, Type, UnOp,
+};
+
+/// A value produced by a generator, alongside the name it suffixes the name of the case with.
+type Value = (String, Expr);
+
+/// Expands the generators specified by each of `cases`.
+pub(crate) fn expand(cases: Vec<TestCase>) -> Result<Vec<TestCase>> {
+    let mut expanded = Vec::with_capacity(cases.len());
+
+    for case in cases {
+        let args = match &case.args.body {
+            CaseBody::Args(args) => args.args.iter().collect(),
+            CaseBody::Fields(fields) => fields.iter().map(|field| &field.expr).collect(),
+            CaseBody::Overrides(_) => {
+                unreachable!("inheritance is resolved before generators are expanded")
+            }
+        };
+        let generated = generated_values(args)?;
+
+        if generated.iter().all(Option::is_none) {
+            expanded.push(case);
+            continue;
+        }
+
+        // Property cases already produce many inputs, from the strategies in place of their args.
+        if let Some(property) = &case.args.property {
+            return Err(Error::new_spanned(
+                property,
+                "property cases can't specify generators in place of arguments",
+            ));
+        }
+
+        for combination in combinations(&generated) {
+            let mut case = case.clone();
+            let mut suffixes = Vec::new();
+            let mut values = combination.into_iter();
+            let mut replace = |arg: &mut Expr| {
+                if let Some((suffix, value)) = values.next().expect("a value for every arg") {
+                    suffixes.push(suffix.as_str());
+                    *arg = value.clone();
+                }
+            };
+
+            match &mut case.args.body {
+                CaseBody::Args(args) => args.args.iter_mut().for_each(&mut replace),
+                CaseBody::Fields(fields) => {
+                    fields.iter_mut().for_each(|field| replace(&mut field.expr))
+                }
+                CaseBody::Overrides(_) => unreachable!(),
+            }
+
+            case.fn_name = format_ident!(
+                "{}_{}",
+                case.fn_name.unraw(),
+                suffixes.join("_"),
+                span = case.fn_name.span()
+            );
+            expanded.push(case);
+        }
+    }
+
+    Ok(expanded)
+}
+
+/// Produces the values of the generator specified by each arg, if any.
+fn generated_values(args: Vec<&Expr>) -> Result<Vec<Option<Vec<Value>>>> {
+    args.into_iter()
+        .map(|arg| match arg {
+            Expr::Call(call) if is_generator(&call.func, &["boundaries"]) => {
+                boundaries(call).map(Some)
+            }
+            Expr::Path(_) if is_generator(arg, &["strings", "edge"]) => {
+                Ok(Some(strings(arg.span())))
+            }
+            Expr::Path(_) if is_generator(arg, &["bytes", "edge"]) => Ok(Some(bytes(arg.span()))),
+            _ => Ok(None),
+        })
+        .collect()
+}
+
+/// Identifies whether `expr` is the path of a generator, qualified by the crate
+/// (e.g. `test_gen::boundaries`), so generators can't be confused with a user's own items.
+///
+/// As a procedural macro crate can't export anything other than macros, such paths
+/// can never resolve to anything, unless the user has their own module named `test_gen`.
+fn is_generator(expr: &Expr, name: &[&str]) -> bool {
+    let path = match expr {
+        Expr::Path(ExprPath {
+            qself: None, path, ..
+        }) => path,
+        _ => return false,
+    };
+    let segments = path.segments.iter().map(|segment| &segment.ident);
+
+    segments.eq(Some(&"test_gen").into_iter().chain(name))
+        && path
+            .segments
+            .iter()
+            .all(|segment| segment.arguments.is_empty())
+}
+
+/// Identifies whether `expr` is the single identifier `name`.
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Path(path) if path.qself.is_none() && path.path.is_ident(name))
+}
+
+/// Produces every combination of the values of each generator,
+/// with args which aren't generated being represented by `None`.
+fn combinations(generated: &[Option<Vec<Value>>]) -> Vec<Vec<Option<&Value>>> {
+    generated
+        .iter()
+        .fold(vec![Vec::new()], |combinations, values| {
+            let values = match values {
+                Some(values) => values.iter().map(Some).collect(),
+                None => vec![None],
+            };
+
+            combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push(*value);
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
+/// The integer types boundary values can be produced for, and whether they're signed.
+const INTEGERS: &[(&str, bool)] = &[
+    ("u8", false),
+    ("u16", false),
+    ("u32", false),
+    ("u64", false),
+    ("u128", false),
+    ("usize", false),
+    ("i8", true),
+    ("i16", true),
+    ("i32", true),
+    ("i64", true),
+    ("i128", true),
+    ("isize", true),
+];
+
+/// Produces the boundary values of an integer type, from `test_gen::boundaries(T)`,
+/// or `test_gen::boundaries(T, around = N)`, additionally producing `N` and the values either side of it.
+fn boundaries(call: &ExprCall) -> Result<Vec<Value>> {
+    let span = call.span();
+    let mut args = call.args.iter();
+    let ty = args
+        .next()
+        .and_then(|ty| syn::parse2::<Type>(quote!(#ty)).ok())
+        .ok_or_else(|| {
+            Error::new(
+                span,
+                "expected an integer type, e.g. `test_gen::boundaries(u16)`",
+            )
+        })?;
+    let name = quote!(#ty).to_string();
+    let signed = INTEGERS
+        .iter()
+        .find(|(integer, _)| *integer == name)
+        .map(|(_, signed)| *signed)
+        .ok_or_else(|| Error::new_spanned(&ty, "expected an integer type"))?;
+
+    let lit = |value: i128| -> Expr {
+        let lit = LitInt::new(&format!("{}{}", value.unsigned_abs(), name), span);
+
+        if value < 0 {
+            parse_quote_spanned!(span=> -#lit)
+        } else {
+            parse_quote_spanned!(span=> #lit)
+        }
+    };
+
+    let mut values: Vec<Value> = vec![(String::from("min"), parse_quote_spanned!(span=> #ty::MIN))];
+
+    // The minimum of unsigned types is already zero,
+    // so the boundaries around zero are redundant.
+    if signed {
+        values.push((
+            String::from("min_plus_1"),
+            parse_quote_spanned!(span=> #ty::MIN + 1),
+        ));
+        values.push((String::from("minus_1"), lit(-1)));
+        values.push((String::from("zero"), lit(0)));
+        values.push((String::from("one"), lit(1)));
+    } else {
+        values.push((String::from("min_plus_1"), lit(1)));
+    }
+
+    values.push((
+        String::from("max_minus_1"),
+        parse_quote_spanned!(span=> #ty::MAX - 1),
+    ));
+    values.push((String::from("max"), parse_quote_spanned!(span=> #ty::MAX)));
+
+    if let Some(arg) = args.next() {
+        let around = self::around(arg)?;
+
+        if !data::fits_in(&around.to_string(), &name) {
+            return Err(Error::new_spanned(
+                arg,
+                match name.as_str() {
+                    "usize" | "isize" => format!(
+                        "`{}` isn't a valid `{}` on every target, which are treated as 32-bit",
+                        around, name
+                    ),
+                    _ => format!("`{}` isn't a valid `{}`", around, name),
+                },
+            ));
+        }
+
+        // Values either side of `around` are skipped where they're out of range.
+        for (suffix, value) in [
+            ("around_minus_1", around.checked_sub(1)),
+            ("around", Some(around)),
+            ("around_plus_1", around.checked_add(1)),
+        ] {
+            if let Some(value) = value.filter(|value| data::fits_in(&value.to_string(), &name)) {
+                values.push((String::from(suffix), lit(value)));
+            }
+        }
+    }
+
+    if let Some(arg) = args.next() {
+        return Err(Error::new_spanned(arg, "unexpected argument"));
+    }
+
+    Ok(values)
+}
+
+/// Parses the integer of an `around = N` argument.
+fn around(arg: &Expr) -> Result<i128> {
+    let error = || Error::new_spanned(arg, "expected `around = N`, where `N` is an integer");
+
+    let value = match arg {
+        Expr::Assign(ExprAssign { left, right, .. }) if is_ident(left, "around") => right,
+        _ => return Err(error()),
+    };
+
+    let (negative, lit) = match &**value {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => (true, &**expr),
+        value => (false, value),
+    };
+
+    match lit {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int
+            .base10_parse::<i128>()
+            .map(|value| if negative { -value } else { value }),
+        _ => Err(error()),
+    }
+}
+
+/// The entries of the `test_gen::strings::edge` corpus, and the names of their cases.
+const STRINGS: &[(&str, &str)] = &[
+    ("empty", ""),
+    ("space", " "),
+    ("whitespace", " \t\n\r\u{b}\u{c}"),
+    ("unicode_whitespace", "\u{a0}\u{2003}\u{2028}\u{3000}"),
+    ("crlf", "a\r\nb"),
+    ("nul", "\0"),
+    ("nul_within", "a\0b"),
+    ("control", "\u{1b}[0m\u{7f}"),
+    ("zero_width", "a\u{200b}b\u{feff}"),
+    ("combining", "e\u{301}"),
+    (
+        "stacked_combining",
+        "a\u{300}\u{301}\u{302}\u{303}\u{304}\u{305}\u{306}\u{307}",
+    ),
+    ("rtl", "\u{5e9}\u{5dc}\u{5d5}\u{5dd}"),
+    ("bidi_override", "\u{202e}abc\u{202c}"),
+    ("surrogate_adjacent", "\u{d7ff}\u{e000}"),
+    ("max_char", "\u{10ffff}"),
+    ("emoji", "\u{1f980}"),
+    ("emoji_zwj", "\u{1f469}\u{200d}\u{1f469}\u{200d}\u{1f467}"),
+    ("flag", "\u{1f1fa}\u{1f1f3}"),
+];
+
+/// The length of the `long` entry of the `test_gen::strings::edge` corpus,
+/// which is longer than can be indexed by a `u16`.
+const LONG_LEN: usize = 65_537;
+
+/// Produces the entries of the `test_gen::strings::edge` corpus, as `&'static str`s.
+fn strings(span: Span) -> Vec<Value> {
+    let long = "a".repeat(LONG_LEN);
+
+    STRINGS
+        .iter()
+        .map(|(name, value)| (*name, *value))
+        .chain(Some(("long", long.as_str())))
+        .map(|(name, value)| {
+            let lit = LitStr::new(value, span);
+            (name.to_owned(), parse_quote_spanned!(span=> #lit))
+        })
+        .collect()
+}
+
+/// The entries of the `test_gen::bytes::edge` corpus, and the names of their cases.
+const BYTES: &[(&str, &[u8])] = &[
+    ("empty", b""),
+    ("nul", b"\0"),
+    ("invalid_utf8", b"\xc3\x28"),
+    ("lone_continuation", b"\x80"),
+    ("truncated", b"\xe2\x82"),
+    ("overlong", b"\xc0\xaf"),
+    ("surrogate", b"\xed\xa0\x80"),
+    ("above_max_char", b"\xf4\x90\x80\x80"),
+    ("utf8_bom", b"\xef\xbb\xbfa"),
+    ("utf16_le_bom", b"\xff\xfea\0"),
+    ("utf16_be_bom", b"\xfe\xff\0a"),
+    ("ff_run", &[0xff; 256]),
+    ("nul_run", &[0; 256]),
+];
+
+/// Produces the entries of the `test_gen::bytes::edge` corpus, as `&'static [u8]`s.
+fn bytes(span: Span) -> Vec<Value> {
+    BYTES
+        .iter()
+        .map(|(name, value)| {
+            let lit = LitByteStr::new(value, span);
+            (name.to_string(), parse_quote_spanned!(span=> &#lit[..]))
+        })
+        .collect()
+}
@@ -27,7 +27,7 @@
 //!
 //! Fruits:
 //! ``` no_run
-#![doc = doctest_example!("fruits")]
+#![doc = include_str!("../tests/doctest_example_fruits.rs")]
 //! ```
 //!
 //! # License
@@ -39,17 +39,60 @@
 //! [LICENSE]: https://github.com/DunnAnDusted/test_gen/blob/main/LICENSE
 #![warn(missing_docs)]
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::ToTokens;
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use std::collections::HashMap;
 use syn::{
-    braced, parenthesized,
-    parse::{Parse, ParseStream, Result},
+    braced,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream, Parser, Result},
     parse_quote,
     punctuated::Punctuated,
     token::{Brace, Paren},
-    Attribute, Error, Expr, Ident, Path, Token, Type,
+    Attribute, Error, Expr, ExprCall, ExprPath, ExprStruct, FieldValue, Ident, LitInt, LitStr,
+    Member, Path, Token, Type,
 };
 
+mod bench;
+mod data;
+mod doctest;
+mod generators;
+mod suites;
+mod support;
+mod timeout;
+mod types;
+mod variants;
+
+/// Custom keywords, used by the clauses of `test_gen`.
+mod kw {
+    syn::custom_keyword!(assert_impl);
+    syn::custom_keyword!(cases);
+    syn::custom_keyword!(check);
+    syn::custom_keyword!(csv);
+    syn::custom_keyword!(each);
+    syn::custom_keyword!(exhaustive);
+    syn::custom_keyword!(extends);
+    syn::custom_keyword!(from);
+    syn::custom_keyword!(glob);
+    syn::custom_keyword!(golden);
+    syn::custom_keyword!(instantiate);
+    syn::custom_keyword!(json);
+    syn::custom_keyword!(name);
+    syn::custom_keyword!(of);
+    syn::custom_keyword!(over);
+    syn::custom_keyword!(property);
+    syn::custom_keyword!(regressions);
+    syn::custom_keyword!(roundtrip);
+    syn::custom_keyword!(snapshot);
+    syn::custom_keyword!(suite);
+    syn::custom_keyword!(toml);
+    syn::custom_keyword!(tsv);
+    syn::custom_keyword!(variant);
+    syn::custom_keyword!(vs);
+    syn::custom_keyword!(with);
+}
+
 /// A shorthand helper macro, for including test files as documentation examples.
 macro_rules! doctest_example {
     ($file:literal) => {
@@ -108,6 +151,390 @@ macro_rules! doctest_example {
 ///
 /// [`Termination`]: std::process::Termination
 ///
+/// Specifying `struct` or `enum` in place of `fn`, the cases of the block instead construct a value
+/// of a tuple struct or enum variant (or through a constructor function), which is discarded,
+/// unless returned by the case. A `check = path` clause specifies a function which checks
+/// an invariant of every value, taking it by reference, which may panic, or return
+/// a `bool` or `Result`. Cases can also compare the `Debug` output of their value,
+/// with `#[expect_debug("...")]`.
+///
+/// Example of constructor cases:
+/// ``` no_run
+#[doc = doctest_example!("constructors")]
+/// ```
+///
+/// Specifying `const fn` in place of `fn`, each case is instead an assertion, evaluated at compile time,
+/// so a failing case fails the build itself, with an error naming the case. Cases assert
+/// the helper function returns `true`, unless they specify the value it's expected to produce,
+/// with `== expected` following their arguments. As cases are evaluated in a constant,
+/// they can't be property cases, or specify return types, or attributes only applicable to tests.
+///
+/// Example of `const fn` cases:
+/// ``` no_run
+#[doc = doctest_example!("const_cases")]
+/// ```
+/// Note: Expected values are compared with `==`, which can only compare primitive types
+/// (e.g. integers, `bool` and `char`) in a constant, on stable Rust.
+///
+/// Where a helper function takes a single struct,
+/// cases may instead be specified as the fields of a struct literal,
+/// using a `with` clause to specify the struct type,
+/// and optionally, a base expression for any fields a case doesn't specify.
+///
+/// Example of struct-literal cases:
+/// ``` no_run
+#[doc = doctest_example!("struct_literal_cases")]
+/// ```
+///
+/// Cases which only differ slightly from another,
+/// can instead extend that case, specifying only the arguments they replace,
+/// either by their index, or in the case of struct-literal cases, by their field name.
+///
+/// Example of case inheritance:
+/// ``` no_run
+#[doc = doctest_example!("case_inheritance")]
+/// ```
+///
+/// Where cases pass the variants of an enum to the helper function,
+/// an `exhaustive over` clause can be used to ensure every variant is passed by some case,
+/// with the compiler reporting any missing variants, as it would for a non-exhaustive `match`.
+/// Variants must be specified by a path through the enum (e.g. `Fruit::Apple`),
+/// but can be specified in any argument, or field of a struct-literal case.
+/// Calls of associated functions (e.g. `Fruit::new("pear")`) are ignored,
+/// being told apart from tuple variants by their lowercase names.
+///
+/// Example of exhaustive cases:
+/// ``` no_run
+#[doc = doctest_example!("exhaustive")]
+/// ```
+///
+/// Example of cases missing a variant, which fails to compile:
+/// ``` compile_fail,E0004
+/// use test_gen::test_gen;
+///
+/// enum Fruit {
+///     Apple,
+///     Pear,
+/// }
+///
+/// impl Fruit {
+///     fn new(name: &str) -> Self {
+///         match name {
+///             "apple" => Self::Apple,
+///             _ => Self::Pear,
+///         }
+///     }
+/// }
+///
+/// fn assert_apple(fruit: Fruit, is_apple: bool) {
+///     assert_eq!(matches!(fruit, Fruit::Apple), is_apple);
+/// }
+///
+/// // "non-exhaustive patterns: `&Fruit::Pear` not covered",
+/// // as constructing it with `Fruit::new` doesn't count towards the variants covered
+/// test_gen! {
+///     fn assert_apple exhaustive over Fruit => {
+///         apple: {
+///             (Fruit::Apple, true)
+///         },
+///         pear: {
+///             (Fruit::new("pear"), false)
+///         },
+///     }
+/// }
+/// ```
+///
+/// Rather than listing the boundaries of an integer parameter by hand,
+/// `test_gen::boundaries(T)` can be specified in place of the argument, for any primitive integer type,
+/// expanding the case into a case for each of `MIN`, `MIN + 1`, `-1`, `0`, `1`, `MAX - 1` and `MAX`
+/// (skipping those which are redundant for unsigned types). `test_gen::boundaries(T, around = N)`
+/// additionally produces `N`, and the values either side of it.
+/// The path must be qualified by the crate, so calls of any function of the user's named `boundaries`,
+/// are passed to the helper function as they are.
+///
+/// As the width of `usize` and `isize` depends on the target, `N` must be within the range
+/// of `u32` and `i32` respectively for those types, so the cases are the same on any target.
+///
+/// Expanded cases are named after the case and the boundary (e.g. `len_max_minus_1`),
+/// and cases specifying several generators are expanded into every combination of their values.
+///
+/// Example of boundary values:
+/// ``` no_run
+#[doc = doctest_example!("boundaries")]
+/// ```
+///
+/// Similarly, `test_gen::strings::edge` and `test_gen::bytes::edge` expand a case into a case
+/// for every entry of a corpus of edge cases, shipped with the crate, as `&'static str`s and `&'static [u8]`s.
+/// Strings include empty, whitespace and very long strings, NULs, combining marks,
+/// right-to-left text, characters either side of the surrogates, and emoji ZWJ sequences,
+/// whilst bytes include invalid UTF-8, byte order marks, and runs of `0xFF` and `NUL`.
+///
+/// Example of edge-case corpora:
+/// ``` no_run
+#[doc = doctest_example!("corpora")]
+/// ```
+///
+/// Where the helper function reimplements another function (e.g. an optimised version),
+/// the reference implementation can be specified after the helper function with `vs`.
+/// Every case then calls both with the same arguments, and fails if their outputs differ,
+/// reporting the name of the case, and both outputs, which must implement `PartialEq` and `Debug`.
+///
+/// Arguments are evaluated separately for each implementation,
+/// and the output of the helper function is used as the output of the case.
+///
+/// Example of differential testing:
+/// ``` no_run
+#[doc = doctest_example!("differential")]
+/// ```
+///
+/// Encoding and decoding functions can be tested together with a round-trip block,
+/// specifying both functions after `roundtrip`. The value of every case is encoded, then decoded,
+/// and compared with the decoded value, which must implement `PartialEq` and `Debug`.
+///
+/// Values are passed to the encoding function by reference, as is the encoded value
+/// to the decoding function, which may return the decoded value, or a `Result` or `Option` of it.
+/// Cases with the `canonical` attribute instead specify an encoded value,
+/// which must be reproduced by encoding the value it decodes to.
+///
+/// Example of round-trip cases:
+/// ``` no_run
+#[doc = doctest_example!("roundtrip")]
+/// ```
+///
+/// Rather than a single set of arguments, property cases specify strategies producing arguments,
+/// with the case being run on a number of pseudo-random inputs produced by them (256 by default).
+/// Strategies include ranges, `any::<T>()` for integers, `bool`, `char` and `String`,
+/// `just(value)`, and `vec_of(strategy, length)`, and the helper function may report a failure by
+/// panicking, or by returning `false` or an `Err`.
+///
+/// Inputs are produced deterministically, from a seed derived from the name of the case.
+/// Failing inputs are reported alongside the seed they were produced from, which is used for
+/// the first input instead, when set as `TEST_GEN_SEED`.
+///
+/// Failing inputs are then shrunk to the simplest input still failing, with integers and floats
+/// shrinking towards zero, and `Vec`s and `String`s shrinking by removing elements or characters.
+/// The shrunk input is reported as a case, ready to be added to the block as a regression:
+/// ```text
+/// add it to the block as a case:
+///
+///     ascending_regression: { (vec![0, 1, 0]) },
+///
+/// or as a line of a regressions file:
+///
+///     (vec![0, 1, 0])
+/// ```
+///
+/// Where the crate depends on `proptest`, its strategies can also be used, with `proptest(strategy)`.
+/// Only cases specifying such a strategy refer to `proptest`, and values they produce aren't shrunk.
+///
+/// Example of property cases:
+/// ``` no_run
+#[doc = doctest_example!("property_cases")]
+/// ```
+///
+/// Inputs which previously caused a failure (e.g. shrunk inputs of property cases) can be kept
+/// in a regressions file, with the `regressions` clause. Each line of the file specifies
+/// the arguments of a case, within parentheses, and is loaded as a case named after the stem
+/// of the file, and the line it's on (e.g. `insertion_sort_0003`). Empty lines and comments are skipped.
+///
+/// Example of replaying regressions:
+/// ``` no_run
+#[doc = doctest_example!("regressions")]
+/// ```
+///
+/// Cases can also be loaded from CSV or TSV files, read whilst the macro is expanded,
+/// relative to the directory containing the crate's `Cargo.toml`.
+///
+/// The first row of the file is a header, which must include a `name` column,
+/// naming each case, with the remaining columns being passed to the helper function in order.
+/// Values are parsed as Rust expressions, unless their column is annotated with a type
+/// (e.g. `input: str`), in which case they are parsed as a literal of that type.
+///
+/// Example of loading cases from a CSV file:
+/// ``` no_run
+#[doc = doctest_example!("data_files")]
+/// ```
+///
+/// With the `json` or `toml` features enabled, cases can similarly be loaded from JSON or TOML files,
+/// from a table mapping the name of each case to its arguments. Arrays are passed as the
+/// arguments of the case, whilst any other value is passed as its only argument.
+///
+/// Values are converted into literals, with nested arrays being converted into `Vec`s.
+/// Tables are converted into struct literals of the type specified by `as`.
+/// JSON's `null` is rejected, as other values aren't wrapped in `Some`,
+/// so optional fields are left to the base expression of the type instead.
+///
+/// Example of loading cases from a JSON file:
+#[cfg_attr(feature = "json", doc = "``` no_run")]
+#[cfg_attr(not(feature = "json"), doc = "``` text")]
+#[doc = doctest_example!("json")]
+/// ```
+///
+/// Example of loading cases from a TOML file:
+#[cfg_attr(feature = "toml", doc = "``` no_run")]
+#[cfg_attr(not(feature = "toml"), doc = "``` text")]
+#[doc = doctest_example!("toml")]
+/// ```
+/// Note: The examples are only compiled as doctests with their feature enabled.
+///
+/// Where each case is instead a fixture file, a case can be loaded for every file matching
+/// a glob pattern, supporting `*` and `?` within a path component, and `**` for any number of directories.
+/// Files are passed to the helper function as their path, or their contents, with `as str` or `as bytes`.
+///
+/// Cases are named after the path of each file, relative to the leading directories of the pattern,
+/// and without its extension.
+///
+/// Example of loading cases from fixture files:
+/// ``` no_run
+#[doc = doctest_example!("fixture_files")]
+/// ```
+/// Note: Changes to the contents of matched files cause the crate to be rebuilt,
+/// but files added (or removed) afterwards **aren't** picked up by themselves, and the cases
+/// produced go stale until the crate is rebuilt for some other reason. Procedural macros
+/// have no stable means of tracking a directory, only the files they include, so the directory
+/// must instead be tracked by a build script of the crate, which reruns when any file within it
+/// is added, removed or changed:
+/// ``` no_run
+/// // Within `main` of the crate's `build.rs`
+/// println!("cargo:rerun-if-changed=tests/fixtures");
+/// ```
+///
+/// Where the behaviour being tested is best described by the output it produces,
+/// cases can instead compare the output of the helper function with an expected output,
+/// stored in a "golden" file. The output must implement [`Display`], and any difference is reported
+/// as a line diff. Golden cases are loaded for every input file matching a glob pattern,
+/// or specified individually with the `golden` attribute.
+///
+/// Input files are passed to the helper function in the same way as fixture files,
+/// but as a `&str` by default, and are each compared with the file of the same name,
+/// with an `out` extension. When the tests are run with `TEST_GEN_BLESS=1` set,
+/// the expected outputs are instead rewritten with the output of each case.
+///
+/// Example of golden cases:
+/// ``` no_run
+#[doc = doctest_example!("golden_files")]
+/// ```
+///
+/// [`Display`]: std::fmt::Display
+///
+/// Similarly, specifying `snapshot` as the return type compares the value returned by the helper
+/// function with a snapshot, stored in `tests/snapshots`, and named after the module and the case.
+/// Values are formatted with `Debug`, or `Display` where specified with `snapshot(Display)`.
+///
+/// Snapshots which don't exist yet, or don't match, cause the case to fail,
+/// and the new snapshot to be written as a pending `.snap.new` file, alongside the snapshot.
+/// Pending snapshots are accepted by removing their `.new` extension,
+/// or by running the tests with `TEST_GEN_BLESS=1` set.
+///
+/// Example of snapshot cases:
+/// ``` no_run
+#[doc = doctest_example!("snapshots")]
+/// ```
+///
+/// Cases which shouldn't compile (e.g. calls a type-state API disallows) can be marked with
+/// `#[compile_fail]`, or `#[compile_fail(E0277)]` to expect a specific error code. Rather than a test,
+/// they produce a hidden item, only compiled by `rustdoc`, documented by a `compile_fail` doctest
+/// calling the helper function with the arguments of the case. Only `cfg` attributes apply to them.
+///
+/// Doctests are compiled as a separate crate, so compile-fail cases are only run by
+/// `cargo test --doc`, from blocks in a library (outside of `#[cfg(test)]` modules),
+/// and can only refer to public items. `crate` is replaced by the name of the crate,
+/// so public items can still be referred to through it. Any other path would fail to resolve,
+/// satisfying the case for the wrong reason, so the helper function and every path
+/// within the arguments must be qualified by `crate::`, or absolute (e.g. `::std::vec::Vec`),
+/// with any others failing to compile the block.
+///
+/// Example of compile-fail cases:
+/// ``` no_run
+#[doc = doctest_example!("compile_fail")]
+/// ```
+/// Note: Any error satisfies a compile-fail case (e.g. an item not being public),
+/// and error codes are only checked by nightly toolchains, so cases should be checked
+/// to fail for the expected reason, by removing the attribute when they're written.
+///
+/// Blocks can also instantiate a suite of generic checks, defined by [`test_gen_suite!`],
+/// for each of a list of types, with `instantiate suite name for [Types]`.
+///
+/// Similarly, blocks can assert a list of types satisfy some bounds, with
+/// `assert_impl { Bounds } for [Types]`. Each type is checked at compile time, at no cost,
+/// as a case named after the type in snake case, unless renamed with `as`
+/// (e.g. `(u8, u8) as pair`). Attributes before `assert_impl` apply to every case,
+/// whilst attributes before a type (e.g. `cfg`) only apply to its case.
+/// Types aren't required to be `Sized`, unless it's one of the bounds.
+/// Names must be unique amongst types with the same `cfg` attributes, so types sharing
+/// the last segment of their path (e.g. `Vec<u8>` and `Vec<u16>`) must be renamed,
+/// unless they're only compiled for different configurations.
+///
+/// Example of asserting trait bounds:
+/// ``` no_run
+#[doc = doctest_example!("assert_impl")]
+/// ```
+///
+/// Example of a type missing a bound, which fails to compile:
+/// ``` compile_fail,E0277
+/// use std::rc::Rc;
+/// use test_gen::test_gen;
+///
+/// pub struct Session {
+///     pub user: Rc<str>,
+/// }
+///
+/// // "`Rc<str>` cannot be sent between threads safely", required by a bound in `session`
+/// test_gen! {
+///     assert_impl { Send } for [
+///         String,
+///         Session,
+///     ]
+/// }
+/// ```
+///
+/// The cases of a block can also be benchmarked, calling the helper function with their arguments,
+/// by specifying `#[generate(benches)]`, or `#[generate(tests, benches)]` to produce both.
+/// Benchmarks are nightly `#[bench]` functions, named after their case, prefixed by `bench_`,
+/// requiring `#![feature(test)]` and `extern crate test;` at the root of the crate.
+///
+/// Specifying `#[generate(benches = criterion)]` instead produces `criterion` benchmarks,
+/// named after their case, and registered in a group named after the helper function,
+/// by a function named after it, suffixed by `_benches`, to be passed to `criterion_group!`.
+/// They aren't behind a cargo feature of `test_gen`, as the benchmarks refer to `::criterion` directly,
+/// rather than through `test_gen`, so only the crate benchmarking its cases needs to depend on it,
+/// usually as a dev-dependency, and `test_gen` itself never does.
+///
+/// Example of criterion benchmarks:
+/// ``` no_run
+#[doc = doctest_example!("benches")]
+/// ```
+/// Only `cfg` attributes apply to benchmarks, and cases which should panic aren't benchmarked.
+///
+/// Alternatively, cases can be checked against a performance budget, specified by
+/// `#[budget(ms = 5, iterations = 1000)]` (or `us` for microseconds), before a case or the block.
+/// The helper function is called repeatedly (100 times, unless specified otherwise), failing
+/// the case if the median time taken exceeds the budget, reporting the minimum, median and
+/// 95th percentile. Budgets of cases override the budget of the block, and only the helper function
+/// is timed, not any comparisons of its output, which are made afterwards, as usual.
+///
+/// Example of performance budgets:
+/// ``` no_run
+#[doc = doctest_example!("budget")]
+/// ```
+/// Note: Times depend on the machine running the tests, and whether they're optimised,
+/// so budgets should be generous, catching regressions by orders of magnitude, rather than percentages.
+///
+/// Cases which may hang can be given a timeout, specified by `#[timeout(secs = 2)]`
+/// (or `ms` for milliseconds), before a case or the block, with timeouts of cases overriding
+/// the timeout of the block. The case is run on a worker thread, failing with
+/// "case `name` timed out after 2s" if it doesn't finish in time, whilst its panics are propagated,
+/// so `should_panic` cases behave as usual. As timing out is itself a panic, `should_panic` cases
+/// with a timeout must specify the message they expect, e.g. `#[should_panic(expected = "overflow")]`,
+/// so a case which hangs doesn't pass. Cases returning a value (e.g. `Result`) instead
+/// return the `ExitCode` it reports, so the returned value needn't be `Send`.
+///
+/// Example of timeouts:
+/// ``` no_run
+#[doc = doctest_example!("timeout")]
+/// ```
+/// Note: Threads can't be stopped, so a case which times out keeps running until the tests finish.
+///
 /// Examples of attribute usage:
 /// ``` no_run
 #[doc = doctest_example!("attributes")]
@@ -123,14 +550,134 @@ pub fn test_gen(tokens: TokenStream) -> TokenStream {
     // In this case, using `syn::parse`, and funneling the result
     // through a subsequent method chain, makes this implementation clearer here.
     //
+    // `MacroHelper::restructure` can also fail, where cases can only be validated
+    // against the rest of the block (e.g. struct-literal cases without a `with` clause),
+    // so both errors are funneled into the same compile error.
+    //
     // NOTE: `Into::into` is required here,
     // because `proc_macro2::TokenStream` needs to be converted back
     // to `proc_macro::TokenStream` before it can be returned.
     syn::parse(tokens)
-        .map_or_else(Error::into_compile_error, MacroHelper::restructure)
+        .and_then(Invocation::restructure)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Defines a named set of test cases, which can be shared between `test_gen` blocks.
+///
+/// Where the same cases are used to test a number of helper functions,
+/// rather than being repeated in every block, they can instead be defined once as a case set,
+/// which any number of blocks can then splice into their own cases, with `use cases`.
+///
+/// Case sets are carried by a `macro_rules!` macro of the same name,
+/// so are subject to the same scoping rules, and attributes specified before `name`
+/// (e.g. `#[macro_export]`) are applied to that macro.
+///
+/// # Examples
+///
+/// Example of sharing cases between blocks:
+/// ``` no_run
+#[doc = doctest_example!("case_sets")]
+/// ```
+/// Note: Whilst exported case sets can be used by other crates via their path,
+/// the compiler doesn't allow an exported macro produced by another macro,
+/// to be referred to by its path within the same crate, so should be referred to by name instead.
+#[proc_macro]
+pub fn test_gen_cases(tokens: TokenStream) -> TokenStream {
+    syn::parse(tokens)
+        .map_or_else(Error::into_compile_error, CaseSet::restructure)
+        .into()
+}
+
+/// Defines a named suite of generic checks, which `test_gen` can instantiate for any number of types.
+///
+/// Where a number of types are expected to behave the same way (e.g. implementations of a trait),
+/// rather than repeating the same tests for each of them, the checks can instead be written once,
+/// as functions generic over the type, and listed as a suite. Blocks of the form
+/// `instantiate suite name for [Types]` then produce a module for every type,
+/// containing a test for every check of the suite.
+///
+/// Modules are named after the type in snake case (e.g. `MemStore` is tested by `mem_store`),
+/// unless renamed with `as`, which is required for types which aren't named by a path
+/// (e.g. `(u8, u8) as pair`). Tests are named after their check, unless similarly renamed.
+///
+/// Like case sets, suites are carried by a `macro_rules!` macro of the same name,
+/// so are subject to the same scoping rules, and attributes specified before `name`
+/// are applied to that macro. Attributes specified before `instantiate`
+/// are applied to every test, whilst attributes specified before a type are applied to its module.
+///
+/// # Examples
+///
+/// Example of a suite instantiated for a number of types:
+/// ``` no_run
+#[doc = doctest_example!("suites")]
+/// ```
+/// Note: Checks are called from the module of each type, so are resolved relative to the module
+/// the suite is instantiated in, rather than the one it's defined in.
+#[proc_macro]
+pub fn test_gen_suite(tokens: TokenStream) -> TokenStream {
+    syn::parse(tokens)
+        .map_or_else(Error::into_compile_error, suites::Suite::restructure)
+        .into()
+}
+
+/// Lists every variant of an enum as a test case, which blocks can use with `for each variant of`.
+///
+/// Each case passes its variant to the helper function, and is named after the variant
+/// in snake case (e.g. `BlackBerry` is passed by the case `black_berry`). Variants with fields
+/// are constructed with the `Default` implementation of each field.
+///
+/// Variants are listed by a `macro_rules!` macro, produced alongside the enum, so blocks
+/// must be within the scope of the macro (i.e. after the enum, in the same or a child module),
+/// and refer to the enum through a path ending with its name.
+///
+/// # Examples
+///
+/// Example of a case for every variant:
+/// ``` no_run
+#[doc = doctest_example!("variants")]
+/// ```
+#[proc_macro_derive(TestGenVariants)]
+pub fn test_gen_variants(tokens: TokenStream) -> TokenStream {
+    syn::parse(tokens)
+        .and_then(variants::derive)
+        .unwrap_or_else(Error::into_compile_error)
         .into()
 }
 
+/// The forms of block `test_gen` accepts.
+///
+/// Blocks either specify a helper function and the cases to drive it with,
+/// or a number of types, to instantiate a suite for, or assert the bounds of.
+enum Invocation {
+    Cases(Box<MacroHelper>),
+    Suite(suites::Instantiation),
+    AssertImpl(types::AssertImpl),
+}
+
+impl Invocation {
+    /// Produces the tokens for the block represented by the value.
+    fn restructure(self) -> Result<TokenStream2> {
+        match self {
+            Self::Cases(helper) => helper.restructure(),
+            Self::Suite(instantiation) => instantiation.restructure(),
+            Self::AssertImpl(assertion) => assertion.restructure(),
+        }
+    }
+}
+
+impl Parse for Invocation {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if suites::Instantiation::peek(input) {
+            input.parse().map(Self::Suite)
+        } else if types::AssertImpl::peek(input) {
+            input.parse().map(Self::AssertImpl)
+        } else {
+            input.parse().map(Self::Cases)
+        }
+    }
+}
+
 /// The main type, representing the collective structure of `test_gen`.
 ///
 /// This includes:
@@ -138,37 +685,92 @@ pub fn test_gen(tokens: TokenStream) -> TokenStream {
 /// * The attributes to apply to every test case
 /// * The separator before the helper function
 /// * The helper function for driving the tests
+/// * The decoding function of round-trip blocks
+/// * The reference implementation the helper function is compared with
 /// * The arguments to pass to the helper function for every test
 /// * The default return type for the helper function
+/// * The invariant checked of values constructed by `struct` and `enum` blocks
+/// * The struct type and defaults for struct-literal cases
 /// * The fat arrow before the braces surrounding the test cases
+/// * The sources of any additional test cases
 /// * The values for producing the resulting test
 #[derive(Clone)]
 struct MacroHelper {
     static_attrs: Vec<Attribute>,
-    separator: Separator, // Preserved for span
+    separator: Separator,
     helper: Path,
+    roundtrip: Option<Roundtrip>,
+    reference: Option<Reference>,
     static_args: Option<FnArgs>,
     static_return_type: Option<ReturnType>,
+    invariant: Option<Invariant>,
+    struct_defaults: Option<StructDefaults>,
+    exhaustive: Option<Exhaustive>,
     farrow: Token![=>], // Preserved for span
     braces: Brace,      // Preserved for span
+    sources: Vec<CaseSource>,
     cases: Punctuated<TestCase, Token![,]>,
 }
 
 impl MacroHelper {
     /// Produces the tokens for the test cases represented by the value.
-    fn restructure(self) -> TokenStream2 {
+    fn restructure(self) -> Result<TokenStream2> {
+        // Case sets and variants can only be expanded by the macro carrying them,
+        // so any remaining `use cases` or `for each variant` sources are expanded before anything else.
+        if let Some(index) = self
+            .sources
+            .iter()
+            .position(|source| matches!(source, CaseSource::Use(_) | CaseSource::Variants(_)))
+        {
+            return Ok(self.splice(index));
+        }
+
         // Uses destructuring, due to use `self`,
         // not being supported in quoting macros,
         // as well as allowing for the consumption of `cases`.
         let Self {
-            static_attrs,
+            mut static_attrs,
+            separator,
             helper,
+            roundtrip,
+            reference,
             mut static_args,
             static_return_type,
+            invariant,
+            struct_defaults,
+            exhaustive,
+            sources,
             cases,
             ..
         } = self;
 
+        // Cases loaded from data files precede the cases of the block,
+        // and the files they're loaded from are tracked for changes.
+        let mut tracked = TokenStream2::new();
+        let mut all_cases = Vec::new();
+
+        for source in sources {
+            match source {
+                CaseSource::Use(_) | CaseSource::Variants(_) => {
+                    unreachable!("case sets and variants are spliced before expansion")
+                }
+                CaseSource::Data(source) => {
+                    let (source_cases, tracker) = source.load()?;
+
+                    all_cases.extend(source_cases);
+                    tracked.extend(tracker);
+                }
+                CaseSource::Regressions(source) => {
+                    let file = data::DataFile::read(&source.path)?;
+
+                    all_cases.extend(data::regression_cases(&file)?);
+                    tracked.extend(file.tracker());
+                }
+            }
+        }
+
+        all_cases.extend(cases);
+
         // Ensures any static arguments don't conflict with the case specific args,
         // by dropping the parens, and appending trailing punctuation to the args.
         let static_args = static_args.as_mut().map(|FnArgs { args, .. }| {
@@ -176,279 +778,2417 @@ impl MacroHelper {
             args
         });
         let static_return_type = static_return_type.as_ref();
+        let all_cases = generators::expand(resolve_inheritance(all_cases)?)?;
+
+        // Exhaustiveness is checked by the compiler itself,
+        // so the check is produced alongside the tracked files.
+        if let Some(exhaustive) = exhaustive {
+            tracked.extend(exhaustive.check(&static_attrs, &all_cases));
+        }
+
+        let generate = bench::Generate::take(&mut static_attrs)?;
+        let mut benches = Vec::new();
+
+        // `const fn` cases are evaluated at compile time, as assertions, rather than tests.
+        if let Separator::ConstFn(constness, _) = separator {
+            if generate.benches.is_some() {
+                return Err(Error::new(
+                    constness.span,
+                    "`const fn` cases are evaluated at compile time, so can't be benchmarked",
+                ));
+            }
+
+            if let Some(return_type) = static_return_type {
+                return Err(Error::new_spanned(
+                    return_type,
+                    "`const fn` blocks can't specify a return type",
+                ));
+            }
+
+            return all_cases
+                .into_iter()
+                .map(|case| {
+                    case.const_assertion(
+                        &static_attrs,
+                        &helper,
+                        static_args.as_deref(),
+                        struct_defaults.as_ref(),
+                    )
+                })
+                .chain(Some(Ok(tracked)))
+                .collect();
+        }
+
+        let budget = take_attr(&mut static_attrs, "budget")
+            .map(|attr| bench::Budget::parse(&attr))
+            .transpose()?;
+        let timeout = take_attr(&mut static_attrs, "timeout")
+            .map(|attr| timeout::Timeout::parse(&attr))
+            .transpose()?;
+
+        // Support modules required by any case are emitted once for the block, rather than
+        // within every test, named after the first case, as names of cases are unique.
+        let support = all_cases.first().map(|case| {
+            format_ident!(
+                "__test_gen_{}",
+                case.fn_name.unraw(),
+                span = case.fn_name.span()
+            )
+        });
+        let mut supported = Vec::new();
+        let block = Block {
+            static_attrs: &static_attrs,
+            separator: &separator,
+            helper: &helper,
+            roundtrip: roundtrip.as_ref(),
+            reference: reference.as_ref(),
+            static_args: static_args.as_deref(),
+            static_return_type,
+            invariant: invariant.as_ref(),
+            struct_defaults: struct_defaults.as_ref(),
+            benched: generate.benches.is_some(),
+            budget,
+            timeout,
+        };
 
-        cases
+        let tests = all_cases
             .into_iter()
-            .map(|case| -> TokenStream2 {
-                let TestCase {
-                    fn_name,
-                    args:
-                        CaseArgs {
-                            attrs,
-                            args: FnArgs { args, .. },
-                            return_type,
-                            ..
-                        },
-                    ..
-                } = case;
-                let return_type = return_type.as_ref().or(static_return_type);
+            .map(|case| block.expand(case, support.as_ref(), &mut benches, &mut supported))
+            .collect::<Result<Vec<TokenStream2>>>()?;
+        let support = (generate.tests && !supported.is_empty())
+            .then(|| support.map(|name| support::emit(&name, &supported)));
+        let tests = generate.tests.then_some(tests).into_iter().flatten();
+        let benches = generate
+            .benches
+            .map(|backend| bench::emit(backend, &helper, &static_attrs, benches));
 
-                // #(#VAR)* syntax behaves similarly to `macro_rules!` equivilent,
-                // for items implementing `IntoIterator<Item: ToTokens>`.
-                //
-                // Separating comma already added to `static_args`,
-                // ensuring it's conditional inclusion.
-                parse_quote! {
-                    #(#static_attrs)*
-                    #(#attrs)*
-                    #[test]
-                    fn #fn_name() #return_type {
-                        #helper(#static_args #args)
-                    }
-                }
-            })
-            .collect()
+        Ok(quote! {
+            #support
+            #(#tests)*
+            #benches
+            #tracked
+        })
     }
 }
 
-impl Parse for MacroHelper {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let static_attrs = input.call(Attribute::parse_outer)?;
+/// The pieces of a block shared by all of its cases, once its case sources,
+/// inheritance and generators have been resolved, for expanding each case into its test.
+struct Block<'a> {
+    static_attrs: &'a [Attribute],
+    separator: &'a Separator,
+    helper: &'a Path,
+    roundtrip: Option<&'a Roundtrip>,
+    reference: Option<&'a Reference>,
+    static_args: Option<&'a Punctuated<Expr, Token![,]>>,
+    static_return_type: Option<&'a ReturnType>,
+    invariant: Option<&'a Invariant>,
+    struct_defaults: Option<&'a StructDefaults>,
+    benched: bool,
+    budget: Option<bench::Budget>,
+    timeout: Option<timeout::Timeout>,
+}
 
-        // TODO: A separator preceeding the helper function specification, isn't a great solution,
-        // style wise, but does promote a potentially more helpful error message
-        // in the case of malformed syntax when specifying attributes...
-        let separator = input.parse().map_err(|err| {
-            Error::new(err.span(), "expected attributes, `fn`, `struct` or `enum`")
-        })?;
+/// A case of a block, with its arguments resolved,
+/// and the attributes selecting how it's checked taken from the attributes of its test.
+struct Case<'a> {
+    fn_name: Ident,
+    property: Option<Property>,
+    braces: Brace, // Preserved for span
+    fields: bool,
+    attrs: Vec<Attribute>,
+    args: Punctuated<Expr, Token![,]>,
+    expected: Option<Expected>,
+    return_type: Option<&'a ReturnType>,
+    modes: Modes,
+}
+
+/// The attributes of a case interpreted by `test_gen` itself, rather than being applied to its test.
+struct Modes {
+    compile_fail: Option<Attribute>,
+    budget: Option<Attribute>,
+    timeout: Option<Attribute>,
+    golden: Option<Attribute>,
+    canonical: Option<Attribute>,
+    expect_debug: Option<Attribute>,
+}
+
+impl Modes {
+    /// Names of the attributes interpreted by `test_gen`, each of which a case specifies once.
+    const NAMES: [&'static str; 6] = [
+        "compile_fail",
+        "budget",
+        "timeout",
+        "golden",
+        "canonical",
+        "expect_debug",
+    ];
+
+    /// Removes the attributes interpreted by `test_gen` from the attributes of a case.
+    fn take(attrs: &mut Vec<Attribute>) -> Self {
+        let [compile_fail, budget, timeout, golden, canonical, expect_debug] =
+            Self::NAMES.map(|name| take_attr(attrs, name));
+
+        Self {
+            compile_fail,
+            budget,
+            timeout,
+            golden,
+            canonical,
+            expect_debug,
+        }
+    }
+}
+
+/// The call of the helper function made by a case, in the form the mode of its block calls it.
+///
+/// Budgets, benchmarks and compile-fail cases time, benchmark or compile the call alone,
+/// so it's computed once, and shared with the test of the case,
+/// so they call the helper function exactly as the test does.
+enum CaseCall {
+    /// The helper function is called with the arguments of the case,
+    /// as by plain blocks, constructors, and blocks with a reference implementation.
+    Args(Punctuated<Expr, Token![,]>),
+    /// The helper function of a round-trip block is called with a reference to the value of the case.
+    Encode(Expr),
+    /// The helper function of a round-trip block is called with the value decoded
+    /// from the encoded value of a canonical case.
+    Canonical(Expr),
+}
+
+impl CaseCall {
+    /// Produces the call of the helper function alone, as the test of the case calls it.
+    ///
+    /// Canonical cases call the helper function with a value only their test decodes,
+    /// so modes calling it alone are rejected for them by [`Block::check`].
+    fn helper_call(&self, block: &Block) -> TokenStream2 {
+        let Block {
+            helper,
+            static_args,
+            ..
+        } = block;
+
+        match self {
+            Self::Args(args) => quote!(#helper(#static_args #args)),
+            Self::Encode(value) => quote!(#helper(#static_args &#value)),
+            Self::Canonical(_) => unreachable!("canonical cases can't call the helper alone"),
+        }
+    }
+}
+
+impl Block<'_> {
+    /// Expands `case` into its test, or the doctest of a compile-fail case,
+    /// pushing its benchmark onto `benches`, and the support modules it requires onto `supported`.
+    fn expand(
+        &self,
+        case: TestCase,
+        support: Option<&Ident>,
+        benches: &mut Vec<bench::Bench>,
+        supported: &mut Vec<support::Module>,
+    ) -> Result<TokenStream2> {
+        let TestCase {
+            fn_name,
+            args:
+                CaseArgs {
+                    property,
+                    braces,
+                    mut attrs,
+                    body,
+                    expected,
+                    return_type,
+                    ..
+                },
+            ..
+        } = case;
+        let modes = Modes::take(&mut attrs);
+        let fields = matches!(body, CaseBody::Fields(_));
+        let FnArgs { args, .. } = body.into_fn_args(braces, self.struct_defaults)?;
+        let case = Case {
+            fn_name,
+            property,
+            braces,
+            fields,
+            attrs,
+            args,
+            expected,
+            return_type: return_type.as_ref().or(self.static_return_type),
+            modes,
+        };
+
+        self.check(&case)?;
+
+        let benched = self.benched(&case);
+        let Case {
+            fn_name,
+            property,
+            attrs,
+            args,
+            return_type,
+            modes,
+            ..
+        } = case;
+        let Self {
+            static_attrs,
+            helper,
+            static_args,
+            ..
+        } = self;
+
+        // Property cases are run on inputs produced by their arguments,
+        // so the arguments are replaced by the inputs they produce,
+        // and the outcome of each input is checked, rather than being returned.
+        let (args, strategies, return_type) = match &property {
+            Some(_) => {
+                let inputs = (0..args.len())
+                    .map(|index| -> Expr {
+                        let input = format_ident!("__test_gen_input_{}", index);
+                        parse_quote!(#input)
+                    })
+                    .collect();
+
+                (inputs, Some(args), None)
+            }
+            None => (args, None, return_type),
+        };
+        let call = match (self.roundtrip, &modes.canonical) {
+            (None, _) => CaseCall::Args(args),
+            (Some(_), None) => CaseCall::Encode(args[0].clone()),
+            (Some(_), Some(_)) => CaseCall::Canonical(args[0].clone()),
+        };
+
+        // Compile-fail cases are only compiled by `rustdoc`, as a doctest of an item,
+        // rather than producing a test of their own.
+        if let Some(compile_fail) = &modes.compile_fail {
+            let cfgs = static_attrs.iter().chain(&attrs);
+
+            return doctest::compile_fail(compile_fail, cfgs, &fn_name, call.helper_call(self));
+        }
+
+        if benched {
+            benches.push(bench::Bench::new(
+                &fn_name,
+                static_attrs.iter().chain(&attrs),
+                call.helper_call(self),
+            ));
+        }
+
+        let name = fn_name.unraw().to_string();
+
+        // Budgets of cases override the budget of the block, timing the helper function
+        // alone, before the case is checked as usual.
+        let budget = match &modes.budget {
+            Some(attr) => Some(bench::Budget::parse(attr)?),
+            None => self.budget,
+        };
+        let budget = budget.map(|budget| budget.check(&name, call.helper_call(self)));
+
+        // Support modules required by the case, emitted once for the block.
+        let mut modules = Vec::new();
+
+        // Where the output of the case is neither returned by the test,
+        // or compared with an expected output, it's discarded.
+        let output =
+            (return_type.is_some() || modes.golden.is_some()).then(|| quote!(__test_gen_output));
+
+        let mut call = match call {
+            CaseCall::Args(args) => {
+                // Separating comma already added to `static_args`,
+                // ensuring it's conditional inclusion.
+                let mut call = quote!(#helper(#static_args #args));
+
+                if self.separator.constructor().is_some() {
+                    call = self.construct(call, &name, &modes, &output, &mut modules)?;
+                }
+
+                match self.reference {
+                    Some(reference) => {
+                        self.compare(call, &name, reference, &args, property.is_some(), &output)
+                    }
+                    None => call,
+                }
+            }
+            CaseCall::Encode(value) => {
+                modules.push(support::Module::Roundtrip);
+                self.encode(&value, &name, &output)
+            }
+            CaseCall::Canonical(encoded) => {
+                modules.push(support::Module::Roundtrip);
+                self.decode(&encoded, &name, &output)
+            }
+        };
+
+        // Golden cases compare the output of the helper function with an expected output,
+        // so can't also return it.
+        if let Some(golden) = &modes.golden {
+            let path = golden.parse_args::<LitStr>()?;
+            let path = data::manifest_path(&path.value(), path.span());
+
+            modules.push(support::Module::Golden);
+            call = quote! {
+                __test_gen::golden::check(#path, #call);
+            };
+        }
+
+        // Snapshot cases compare the value returned by the helper function
+        // with the snapshot named after the case, rather than returning it.
+        let return_type = match return_type.and_then(ReturnType::snapshot) {
+            Some(snapshot) => {
+                let value = snapshot.format(call);
+
+                modules.push(support::Module::Snapshot);
+                call = quote! {
+                    __test_gen::snapshot::check(::std::module_path!(), #name, #value);
+                };
+
+                None
+            }
+            None => return_type,
+        };
+
+        // The strategies are evaluated once, as a tuple, producing a tuple of inputs,
+        // with `any`, `just` and `vec_of` in scope for specifying them.
+        if let Some(strategies) = strategies {
+            let iterations = property.as_ref().map_or(Ok(0), Property::iterations)?;
+            // `proptest` is only referred to by cases using its strategies,
+            // as the crate may not depend on it.
+            let proptest = uses_proptest(strategies.to_token_stream()).then(|| {
+                modules.push(support::Module::Proptest);
+                quote!(
+                    use __test_gen::proptest::proptest;
+                )
+            });
+            let inputs =
+                (0..strategies.len()).map(|index| format_ident!("__test_gen_input_{}", index));
+            let strategies = strategies.iter();
+
+            modules.push(support::Module::Property);
+            call = quote! {
+                use __test_gen::property::{any, just, vec_of};
+                #proptest
+
+                __test_gen::property::check(
+                    #name,
+                    #iterations,
+                    (#(#strategies,)*),
+                    |(#(#inputs,)*)| #call,
+                );
+            };
+        }
+
+        if budget.is_some() {
+            modules.push(support::Module::Budget);
+        }
+
+        // Timeouts of cases override the timeout of the block, running the whole case
+        // on a worker thread, including checking its budget.
+        let timeout = match &modes.timeout {
+            Some(attr) => Some(timeout::Timeout::parse(attr)?),
+            None => self.timeout,
+        };
+        let (return_type, body) = match timeout {
+            Some(timeout) => {
+                modules.push(support::Module::Timeout);
+
+                (
+                    return_type.map(|_| quote!(-> ::std::process::ExitCode)),
+                    timeout.run(
+                        &name,
+                        return_type.and_then(ReturnType::ty),
+                        quote!(#budget #call),
+                    ),
+                )
+            }
+            None => (
+                return_type.map(ToTokens::to_token_stream),
+                quote!(#budget #call),
+            ),
+        };
+
+        // Tests refer to the support module of the block as `__test_gen`.
+        let support = (!modules.is_empty()).then(|| {
+            supported.extend(modules);
+            quote!(use #support as __test_gen;)
+        });
+
+        // #(#VAR)* syntax behaves similarly to `macro_rules!` equivilent,
+        // for items implementing `IntoIterator<Item: ToTokens>`.
+        Ok(parse_quote! {
+            #(#static_attrs)*
+            #(#attrs)*
+            #[test]
+            fn #fn_name() #return_type {
+                #support
+                #body
+            }
+        })
+    }
+
+    /// Identifies whether `case` is benchmarked.
+    ///
+    /// Benchmarks only call the helper function, with the arguments of the case,
+    /// so panicking cases would only measure the panic, and aren't benchmarked.
+    fn benched(&self, case: &Case) -> bool {
+        self.benched
+            && case.modes.compile_fail.is_none()
+            && !self
+                .static_attrs
+                .iter()
+                .chain(&case.attrs)
+                .any(|attr| attr.path.is_ident("should_panic"))
+    }
+
+    /// Rejects any combination of the modes of `case` and its block which can't be checked together,
+    /// before any of the case is expanded.
+    fn check(&self, case: &Case) -> Result<()> {
+        let Case {
+            fn_name,
+            property,
+            braces,
+            fields,
+            attrs,
+            args,
+            expected,
+            return_type,
+            modes,
+        } = case;
+        let benched = self.benched(case);
+        let budgeted = modes.budget.is_some() || self.budget.is_some();
+
+        if let Some(Expected { eq, .. }) = expected {
+            return Err(Error::new_spanned(
+                eq,
+                "only cases of `const fn` blocks can specify an expected value",
+            ));
+        }
+
+        if let Some(property) = property {
+            let error = |message| Err(Error::new_spanned(property, message));
+
+            if *fields {
+                return Err(Error::new(
+                    braces.span,
+                    "property cases must specify their strategies as arguments",
+                ));
+            } else if modes.compile_fail.is_some() {
+                return error("property cases can't be compile-fail");
+            } else if benched {
+                return error("property cases can't be benchmarked");
+            } else if budgeted {
+                return error("property cases can't have a budget");
+            } else if let Some(snapshot) = return_type.and_then(ReturnType::snapshot) {
+                return Err(Error::new_spanned(
+                    snapshot,
+                    "property cases can't be compared with a snapshot",
+                ));
+            } else if let Some(golden) = &modes.golden {
+                return Err(Error::new_spanned(
+                    golden,
+                    "property cases can't be compared with an expected output",
+                ));
+            }
+        }
+
+        if let (None, Some(expect_debug)) = (self.separator.constructor(), &modes.expect_debug) {
+            return Err(Error::new_spanned(
+                expect_debug,
+                "only cases of `struct` and `enum` blocks construct a value to expect `Debug` output of",
+            ));
+        }
+
+        match (self.roundtrip, &modes.canonical) {
+            (None, Some(canonical)) => {
+                return Err(Error::new_spanned(
+                    canonical,
+                    "only cases of round-trip blocks can be canonical",
+                ))
+            }
+            (Some(_), _) if args.len() != 1 => {
+                return Err(Error::new(
+                    fn_name.span(),
+                    "round-trip cases take a single value",
+                ))
+            }
+            // Canonical cases call the helper function with a value only their test decodes,
+            // so it can't be called alone.
+            (Some(_), Some(canonical)) if modes.compile_fail.is_some() || benched || budgeted => {
+                return Err(Error::new_spanned(
+                    canonical,
+                    "canonical cases call the helper function with a decoded value, \
+                    so can't be compile-fail, benchmarked, or have a budget",
+                ))
+            }
+            _ => {}
+        }
+
+        if let (Some(_), Some(return_type)) = (&modes.golden, return_type) {
+            return Err(Error::new_spanned(
+                return_type,
+                "golden cases can't specify a return type",
+            ));
+        }
+
+        // Compile-fail cases aren't run, so can't time out.
+        if modes.compile_fail.is_none() && (modes.timeout.is_some() || self.timeout.is_some()) {
+            timeout::Timeout::check_should_panic(self.static_attrs.iter().chain(attrs))?;
+        }
+
+        Ok(())
+    }
+
+    /// Wraps `call`, constructing a value in a `struct` or `enum` block,
+    /// checking the value against the invariant of the block, and any expected `Debug` output.
+    /// The value is only produced where it's compared or returned, otherwise being discarded.
+    fn construct(
+        &self,
+        call: TokenStream2,
+        name: &str,
+        modes: &Modes,
+        output: &Option<TokenStream2>,
+        modules: &mut Vec<support::Module>,
+    ) -> Result<TokenStream2> {
+        let invariant = self.invariant.map(|Invariant { path, .. }| {
+            let invariant = path.to_token_stream().to_string().replace(' ', "");
+
+            quote! {
+                __test_gen::invariant::check(
+                    #name,
+                    #invariant,
+                    #path(&__test_gen_value),
+                );
+            }
+        });
+        let expect_debug = modes
+            .expect_debug
+            .as_ref()
+            .map(|attr| -> Result<TokenStream2> {
+                let expected = attr.parse_args::<LitStr>()?;
+
+                Ok(quote! {
+                    __test_gen::invariant::expect_debug(
+                        #name,
+                        #expected,
+                        &__test_gen_value,
+                    );
+                })
+            })
+            .transpose()?;
+        let value =
+            (output.is_some() || self.reference.is_some()).then(|| quote!(__test_gen_value));
+
+        if invariant.is_some() || expect_debug.is_some() {
+            modules.push(support::Module::Invariant);
+        }
+
+        Ok(quote! {{
+            let __test_gen_value = #call;
+            #invariant
+            #expect_debug
+            #value
+        }})
+    }
+
+    /// Wraps `call`, also calling the reference implementation with the same arguments,
+    /// and producing the output of the helper function once they've been compared.
+    fn compare(
+        &self,
+        call: TokenStream2,
+        name: &str,
+        Reference { path, .. }: &Reference,
+        args: &Punctuated<Expr, Token![,]>,
+        property: bool,
+        output: &Option<TokenStream2>,
+    ) -> TokenStream2 {
+        let Self {
+            helper,
+            static_args,
+            ..
+        } = self;
+
+        // Inputs of property cases are cloned for the reference implementation,
+        // before the helper function consumes them, as they're only produced once.
+        let (cloned, reference_args) = match property {
+            true => {
+                let cloned = (0..args.len())
+                    .map(|index| format_ident!("__test_gen_reference_{}", index))
+                    .collect::<Vec<_>>();
+                let inputs = args.iter();
+
+                (
+                    quote! {
+                        let (#(#cloned,)*) = (#(::std::clone::Clone::clone(&#inputs),)*);
+                    },
+                    quote!(#(#cloned),*),
+                )
+            }
+            false => (TokenStream2::new(), args.to_token_stream()),
+        };
+        let helper_name = helper.to_token_stream().to_string().replace(' ', "");
+        let reference_name = path.to_token_stream().to_string().replace(' ', "");
+
+        quote! {{
+            #cloned
+            let __test_gen_output = #call;
+            let __test_gen_reference = #path(#static_args #reference_args);
+
+            if __test_gen_output != __test_gen_reference {
+                ::std::panic!(
+                    "case `{}` diverged from the reference implementation:\n\n{}: {:?}\n{}: {:?}\n",
+                    #name,
+                    #helper_name,
+                    __test_gen_output,
+                    #reference_name,
+                    __test_gen_reference,
+                );
+            }
+
+            #output
+        }}
+    }
+
+    /// Produces the decoding of `encoded` by the decoding function of a round-trip block.
+    ///
+    /// The decoded value is produced by reference,
+    /// as the traits distinguishing decoding functions are implemented by reference.
+    /// The decoding is bound by reference, so the reference the fallback trait is implemented for
+    /// is borrowed from a local, rather than a temporary dropped before the decoded value is used.
+    fn decoded(&self, encoded: TokenStream2, name: &str) -> TokenStream2 {
+        let static_args = self.static_args;
+        let decode = self.roundtrip.map(|Roundtrip { decode, .. }| decode);
+
+        quote! {
+            use __test_gen::roundtrip::{DecodeOption as _, DecodeResult as _, DecodeValue as _};
+
+            let __test_gen_decoding = &__test_gen::roundtrip::Decoding(
+                #decode(#static_args #encoded),
+            );
+            let __test_gen_decoded = __test_gen::roundtrip::decoded(
+                #name,
+                __test_gen_decoding.decoded(),
+            );
+        }
+    }
+
+    /// Passes `value` through the encoding and decoding functions of a round-trip block,
+    /// producing the encoded value once the decoded value has been compared with it.
+    fn encode(&self, value: &Expr, name: &str, output: &Option<TokenStream2>) -> TokenStream2 {
+        let Self {
+            helper,
+            static_args,
+            ..
+        } = self;
+        let decoded = self.decoded(quote!(&__test_gen_output), name);
+
+        quote! {{
+            let __test_gen_value = #value;
+            let __test_gen_output = #helper(#static_args &__test_gen_value);
+            #decoded
+
+            __test_gen::roundtrip::check(
+                #name,
+                &__test_gen_value,
+                &__test_gen_output,
+                __test_gen_decoded,
+            );
+            #output
+        }}
+    }
+
+    /// Decodes the encoded value of a canonical case, which must be reproduced
+    /// by encoding its decoded value, producing the encoded value once they've been compared.
+    fn decode(&self, encoded: &Expr, name: &str, output: &Option<TokenStream2>) -> TokenStream2 {
+        let Self {
+            helper,
+            static_args,
+            ..
+        } = self;
+        let decoded = self.decoded(quote!(&__test_gen_encoded[..]), name);
+
+        quote! {{
+            let __test_gen_encoded = #encoded;
+            #decoded
+            let __test_gen_output = #helper(#static_args __test_gen_decoded);
+
+            __test_gen::roundtrip::check_canonical(
+                #name,
+                &__test_gen_encoded,
+                __test_gen_decoded,
+                &__test_gen_output,
+            );
+            #output
+        }}
+    }
+}
+
+/// Removes the first attribute named `name` from `attrs`,
+/// for attributes interpreted by `test_gen` itself, rather than being applied to the case.
+fn take_attr(attrs: &mut Vec<Attribute>, name: &str) -> Option<Attribute> {
+    attrs
+        .iter()
+        .position(|attr| attr.path.is_ident(name))
+        .map(|index| attrs.remove(index))
+}
+
+impl MacroHelper {
+    /// Produces an invocation of the case set, or the variants, named by the source at `index`,
+    /// which invokes `test_gen` again, with the cases it carries spliced into the block.
+    ///
+    /// The tokens of the block are passed in two parts, allowing the case set
+    /// to splice its cases into the braces surrounding the test cases.
+    /// Variants are also passed the path of the enum, to construct each variant through.
+    fn splice(mut self, index: usize) -> TokenStream2 {
+        let (path, ty) = match self.sources.remove(index) {
+            CaseSource::Use(UseCases { path, .. }) => (path, None),
+            CaseSource::Variants(VariantCases { path, .. }) => {
+                let name = path
+                    .segments
+                    .last()
+                    .map(|segment| variants::macro_name(&segment.ident))
+                    .expect("paths have at least one segment");
+
+                (name.into(), Some(path))
+            }
+            _ => unreachable!("only `use cases` and `for each variant` sources are spliced"),
+        };
+        let ty = ty.map(|ty| quote!([#ty]));
+
+        if !self.cases.empty_or_trailing() {
+            self.cases.push_punct(Default::default());
+        }
+
+        let mut head = TokenStream2::new();
+        self.head_to_tokens(&mut head);
+
+        let mut cases = TokenStream2::new();
+        self.cases_to_tokens(&mut cases);
+
+        quote! {
+            #path! {
+                @test_gen_splice [::test_gen::test_gen] #ty { #head } { #cases }
+            }
+        }
+    }
+}
+
+/// Resolves any cases extending another case, into standalone cases.
+///
+/// Produces an error, if a case extends an unknown case,
+/// or a chain of cases ultimately extends itself.
+fn resolve_inheritance(cases: Vec<TestCase>) -> Result<Vec<TestCase>> {
+    // Iterates in reverse, so the first of any duplicate names is the one which is extended,
+    // the duplicate itself being left for the compiler to report.
+    let names = cases
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, case)| (case.fn_name.to_string(), index))
+        .collect::<HashMap<_, _>>();
+    let mut resolved = vec![None; cases.len()];
+
+    for index in 0..cases.len() {
+        resolve_case(index, &cases, &names, &mut resolved, &mut Vec::new())?;
+    }
+
+    Ok(cases
+        .into_iter()
+        .zip(resolved)
+        .map(|(case, args)| TestCase {
+            args: args.expect("every case should have been resolved"),
+            ..case
+        })
+        .collect())
+}
+
+/// Resolves the arguments of the case at `index`, including any of the cases it extends.
+///
+/// `chain` tracks the cases currently being resolved, to identify cyclic inheritance.
+fn resolve_case(
+    index: usize,
+    cases: &[TestCase],
+    names: &HashMap<String, usize>,
+    resolved: &mut [Option<CaseArgs>],
+    chain: &mut Vec<usize>,
+) -> Result<CaseArgs> {
+    if let Some(args) = &resolved[index] {
+        return Ok(args.clone());
+    }
+
+    let args = &cases[index].args;
+    let args = match &args.extends {
+        None => args.clone(),
+        Some(Extends { parent, .. }) => {
+            let parent_index = *names.get(&parent.to_string()).ok_or_else(|| {
+                Error::new(parent.span(), format!("unknown parent case `{}`", parent))
+            })?;
+
+            chain.push(index);
+
+            if let Some(start) = chain.iter().position(|&link| link == parent_index) {
+                let cycle = chain[start..]
+                    .iter()
+                    .chain(Some(&parent_index))
+                    .map(|&link| format!("`{}`", cases[link].fn_name))
+                    .collect::<Vec<_>>()
+                    .join(" extends ");
+
+                return Err(Error::new(
+                    parent.span(),
+                    format!("cyclic case inheritance: {}", cycle),
+                ));
+            }
+
+            let parent_args = resolve_case(parent_index, cases, names, resolved, chain)?;
+            chain.pop();
+
+            args.clone().inherit(parent_args)?
+        }
+    };
+
+    resolved[index] = Some(args.clone());
+    Ok(args)
+}
+
+impl Parse for MacroHelper {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let static_attrs = input.call(Attribute::parse_outer)?;
+        let roundtrip_kw = input.parse::<Option<kw::roundtrip>>()?;
+
+        // The separator preceeding the helper distinguishes functions,
+        // from tuple structs and enum variants, which construct a value to be checked instead.
+        let separator = input.parse::<Separator>()?;
+        let helper = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected helper function"))?;
+        let roundtrip = roundtrip_kw
+            .map(|roundtrip| Roundtrip::parse_decoder(roundtrip, input))
+            .transpose()?;
+
+        if let (Some(_), false) = (&roundtrip, matches!(separator, Separator::Fn(_))) {
+            return Err(Error::new_spanned(
+                &separator,
+                "round-trip blocks encode values with a function, so must specify `fn`",
+            ));
+        }
+        let reference = input.call(Reference::try_parse)?;
+
+        if let (Some(_), Some(reference)) = (&roundtrip, &reference) {
+            return Err(Error::new_spanned(
+                reference.vs,
+                "round-trip blocks can't specify a reference implementation",
+            ));
+        }
+
+        if let (Separator::ConstFn(..), Some(reference)) = (&separator, &reference) {
+            return Err(Error::new_spanned(
+                reference.vs,
+                "`const fn` blocks can't specify a reference implementation",
+            ));
+        }
+
+        // Parsing is only attempted, if parenthese are peeked,
+        // as this would indicate the user wishes to specify static args.
+        let static_args = input.peek(Paren).then(|| input.parse()).transpose()?;
+        let static_return_type = input.call(ReturnType::try_parse)?;
+        let invariant = input.call(Invariant::try_parse)?;
+
+        if let (None, Some(invariant)) = (separator.constructor(), &invariant) {
+            return Err(Error::new_spanned(
+                invariant.check,
+                "only `struct` and `enum` blocks construct a value to check an invariant of",
+            ));
+        }
+
+        let struct_defaults = input.call(StructDefaults::try_parse)?;
+        let exhaustive = input.call(Exhaustive::try_parse)?;
+        let farrow = input.parse()?;
+        let cases;
+        let braces = braced!(cases in input);
+
+        // Sources of additional cases precede the cases themselves,
+        // similarly to `use` declarations at the top of a module.
+        let mut sources = Vec::new();
+
+        while CaseSource::peek(&cases) {
+            sources.push(cases.parse()?);
+        }
+
+        // If the contents of `cases` is empty,
+        // `ParseBuffer::parse_terminated` will simply produce an empty
+        // `Punctuated` struct, and no error. On the other hand,
+        // `Punctuated::parse_separated_nonempty` falls back on the parsing error
+        // for `TestCase`, as well as not allow trailing commas,
+        // neither of which is particularly helpful in this case...
+        //
+        // Instead, its explicitly checked whether `cases` is empty,
+        // resulting in a bespoke error which provides an explanation which is actually
+        // helpful. Blocks which only specify case sources are the exception.
+        (cases.is_empty() && sources.is_empty())
+            .then(|| Error::new(cases.span(), "expected test cases"))
+            .map_or_else(|| cases.parse_terminated(TestCase::parse), Result::Err)
+            .map(|cases| Self {
+                static_attrs,
+                separator,
+                helper,
+                roundtrip,
+                reference,
+                static_args,
+                static_return_type,
+                invariant,
+                struct_defaults,
+                exhaustive,
+                farrow,
+                braces,
+                sources,
+                cases,
+            })
+    }
+}
+
+impl MacroHelper {
+    /// Appends the tokens preceeding the braces surrounding the test cases.
+    fn head_to_tokens(&self, tokens: &mut TokenStream2) {
+        // `ToTokens` isn't implemented for anything like `&[T: ToTokens]`
+        // or `(Into)Iterator<Item: ToTokens>`, so appending them iteratively
+        // is about as clean a solution as possible...
+        self.static_attrs
+            .iter()
+            .for_each(|attr| attr.to_tokens(tokens));
+
+        if let Some(roundtrip) = &self.roundtrip {
+            roundtrip.roundtrip.to_tokens(tokens);
+        }
+
+        self.separator.to_tokens(tokens);
+        self.helper.to_tokens(tokens);
+        self.roundtrip.to_tokens(tokens);
+        self.reference.to_tokens(tokens);
+        self.static_args.to_tokens(tokens);
+        self.static_return_type.to_tokens(tokens);
+        self.invariant.to_tokens(tokens);
+        self.struct_defaults.to_tokens(tokens);
+        self.exhaustive.to_tokens(tokens);
+        self.farrow.to_tokens(tokens);
+    }
+
+    /// Appends the tokens within the braces surrounding the test cases.
+    fn cases_to_tokens(&self, tokens: &mut TokenStream2) {
+        self.sources
+            .iter()
+            .for_each(|source| source.to_tokens(tokens));
+        self.cases.to_tokens(tokens);
+    }
+}
+
+impl ToTokens for MacroHelper {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.head_to_tokens(tokens);
+        // Token groups are kind of weird, so uses `surround` to identify the tokens which the
+        // group should surround...
+        self.braces
+            .surround(tokens, |inner| self.cases_to_tokens(inner));
+    }
+}
+
+/// A type representing a source of additional test cases for a block.
+#[derive(Clone)]
+enum CaseSource {
+    Use(UseCases),
+    Variants(VariantCases),
+    Data(DataCases),
+    Regressions(Regressions),
+}
+
+impl CaseSource {
+    /// Identifies whether the stream begins with a case source,
+    /// rather than a test case.
+    ///
+    /// NOTE: `cases` and `regressions` alone could also be the name of a test case,
+    /// so the token following them is also checked.
+    fn peek(input: ParseStream) -> bool {
+        input.peek(Token![use])
+            || input.peek(Token![for])
+            || (input.peek(kw::cases) && input.peek2(kw::from))
+            || (input.peek(kw::regressions) && input.peek2(LitStr))
+    }
+}
+
+impl Parse for CaseSource {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![use]) {
+            input.parse().map(Self::Use)
+        } else if input.peek(Token![for]) {
+            input.parse().map(Self::Variants)
+        } else if input.peek(kw::regressions) {
+            input.parse().map(Self::Regressions)
+        } else {
+            input.parse().map(Self::Data)
+        }
+    }
+}
+
+impl ToTokens for CaseSource {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Use(source) => source.to_tokens(tokens),
+            Self::Variants(source) => source.to_tokens(tokens),
+            Self::Data(source) => source.to_tokens(tokens),
+            Self::Regressions(source) => source.to_tokens(tokens),
+        }
+    }
+}
+
+/// A type representing the use of a named case set, defined by `test_gen_cases`.
+#[derive(Clone)]
+struct UseCases {
+    use_token: Token![use],
+    cases: kw::cases,
+    path: Path,
+    semi: Token![;],
+}
+
+impl Parse for UseCases {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let use_token = input.parse()?;
+        let cases = input.parse()?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected name of case set"))?;
+
+        input.parse().map(|semi| Self {
+            use_token,
+            cases,
+            path,
+            semi,
+        })
+    }
+}
+
+impl ToTokens for UseCases {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.use_token.to_tokens(tokens);
+        self.cases.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+    }
+}
+
+/// A type representing the use of the variants of an enum, listed by `TestGenVariants`.
+#[derive(Clone)]
+struct VariantCases {
+    for_token: Token![for],
+    each: kw::each,
+    variant: kw::variant,
+    of: kw::of,
+    path: Path,
+    semi: Token![;],
+}
+
+impl Parse for VariantCases {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let for_token = input.parse()?;
+        let each = input.parse()?;
+        let variant = input.parse()?;
+        let of = input.parse()?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected enum type"))?;
+
+        input.parse().map(|semi| Self {
+            for_token,
+            each,
+            variant,
+            of,
+            path,
+            semi,
+        })
+    }
+}
+
+impl ToTokens for VariantCases {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.for_token.to_tokens(tokens);
+        self.each.to_tokens(tokens);
+        self.variant.to_tokens(tokens);
+        self.of.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+    }
+}
+
+/// A type representing cases loaded from a data file,
+/// read relative to the manifest directory of the crate being compiled.
+///
+/// Structured formats (e.g. JSON) may also specify the struct type tables are converted into,
+/// whilst glob patterns may specify how matched files are passed to the helper function.
+#[derive(Clone)]
+struct DataCases {
+    cases: kw::cases,
+    from: kw::from,
+    format: DataFormat,
+    path: LitStr,
+    ty: Option<(Token![as], Path)>,
+    semi: Token![;],
+}
+
+impl DataCases {
+    /// Loads the cases from the data file, alongside the tokens tracking it for changes.
+    fn load(&self) -> Result<(Vec<TestCase>, TokenStream2)> {
+        let ty = self.ty.as_ref().map(|(_, ty)| ty);
+
+        // Glob patterns match any number of files,
+        // and instead use `as` to specify how each file is passed to the helper function.
+        //
+        // Golden inputs are passed by their contents by default,
+        // as they're typically text to be transformed by the helper function.
+        if let DataFormat::Glob(_) | DataFormat::Golden(_) = self.format {
+            let mode = match ty {
+                None if matches!(self.format, DataFormat::Golden(_)) => data::GlobMode::Str,
+                None => data::GlobMode::Path,
+                Some(ty) if ty.is_ident("path") => data::GlobMode::Path,
+                Some(ty) if ty.is_ident("str") => data::GlobMode::Str,
+                Some(ty) if ty.is_ident("bytes") => data::GlobMode::Bytes,
+                Some(ty) => {
+                    return Err(Error::new_spanned(ty, "expected `path`, `str` or `bytes`"))
+                }
+            };
+
+            return match self.format {
+                DataFormat::Golden(_) => data::golden_cases(&self.path, mode),
+                _ => data::glob_cases(&self.path, mode),
+            };
+        }
+
+        let file = data::DataFile::read(&self.path)?;
+        let cases = match &self.format {
+            DataFormat::Csv(_) | DataFormat::Tsv(_) if ty.is_some() => {
+                return Err(Error::new_spanned(
+                    ty,
+                    "struct types can only be specified for JSON and TOML files",
+                ))
+            }
+            DataFormat::Csv(_) => data::delimited_cases(&file, ',')?,
+            DataFormat::Tsv(_) => data::delimited_cases(&file, '\t')?,
+            DataFormat::Json(_) => {
+                data::structured_cases(&file, data::Value::from_json(&file)?, ty)?
+            }
+            DataFormat::Toml(_) => {
+                data::structured_cases(&file, data::Value::from_toml(&file)?, ty)?
+            }
+            DataFormat::Glob(_) | DataFormat::Golden(_) => {
+                unreachable!("glob patterns don't name a single file")
+            }
+        };
+
+        Ok((cases, file.tracker()))
+    }
+}
+
+impl Parse for DataCases {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let cases = input.parse()?;
+        let from = input.parse()?;
+        let format = input.parse()?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected path of data file"))?;
+        let ty = input
+            .peek(Token![as])
+            .then(|| -> Result<_> {
+                let as_token = input.parse()?;
+
+                input
+                    .parse()
+                    .map_err(|err| Error::new(err.span(), "expected struct type"))
+                    .map(|ty| (as_token, ty))
+            })
+            .transpose()?;
+
+        input.parse().map(|semi| Self {
+            cases,
+            from,
+            format,
+            path,
+            ty,
+            semi,
+        })
+    }
+}
+
+impl ToTokens for DataCases {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.cases.to_tokens(tokens);
+        self.from.to_tokens(tokens);
+        self.format.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+
+        if let Some((as_token, ty)) = &self.ty {
+            as_token.to_tokens(tokens);
+            ty.to_tokens(tokens);
+        }
+
+        self.semi.to_tokens(tokens);
+    }
+}
+
+/// A type representing previously failing inputs, loaded from a regressions file,
+/// read relative to the manifest directory of the crate being compiled.
+#[derive(Clone)]
+struct Regressions {
+    regressions: kw::regressions,
+    path: LitStr,
+    semi: Token![;],
+}
+
+impl Parse for Regressions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let regressions = input.parse()?;
+        let path = input.parse()?;
+
+        input.parse().map(|semi| Self {
+            regressions,
+            path,
+            semi,
+        })
+    }
+}
+
+impl ToTokens for Regressions {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.regressions.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+    }
+}
+
+/// A type representing the format of a data file cases are loaded from.
+#[derive(Clone)]
+enum DataFormat {
+    Csv(kw::csv),
+    Tsv(kw::tsv),
+    Json(kw::json),
+    Toml(kw::toml),
+    Glob(kw::glob),
+    Golden(kw::golden),
+}
+
+impl Parse for DataFormat {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+
+        if lookahead.peek(kw::csv) {
+            input.parse().map(Self::Csv)
+        } else if lookahead.peek(kw::tsv) {
+            input.parse().map(Self::Tsv)
+        } else if lookahead.peek(kw::json) {
+            input.parse().map(Self::Json)
+        } else if lookahead.peek(kw::toml) {
+            input.parse().map(Self::Toml)
+        } else if lookahead.peek(kw::glob) {
+            input.parse().map(Self::Glob)
+        } else if lookahead.peek(kw::golden) {
+            input.parse().map(Self::Golden)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+impl ToTokens for DataFormat {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Csv(format) => format.to_tokens(tokens),
+            Self::Tsv(format) => format.to_tokens(tokens),
+            Self::Json(format) => format.to_tokens(tokens),
+            Self::Toml(format) => format.to_tokens(tokens),
+            Self::Glob(format) => format.to_tokens(tokens),
+            Self::Golden(format) => format.to_tokens(tokens),
+        }
+    }
+}
+
+/// The type representing the structure of `test_gen_cases`.
+///
+/// This includes:
+///
+/// * The attributes to apply to the produced macro
+/// * The name of the case set
+/// * The cases carried by the case set
+#[derive(Clone)]
+struct CaseSet {
+    attrs: Vec<Attribute>,
+    name_kw: kw::name,
+    eq: Token![=],
+    name: Ident,
+    semi: Token![;],
+    cases: Punctuated<TestCase, Token![,]>,
+}
+
+impl CaseSet {
+    /// Produces the macro carrying the cases of the set.
+    ///
+    /// The macro only has a single rule, which splices its cases into an invocation of `test_gen`,
+    /// produced when a block uses the case set.
+    fn restructure(self) -> TokenStream2 {
+        let Self {
+            attrs,
+            name,
+            mut cases,
+            ..
+        } = self;
+
+        if !cases.empty_or_trailing() {
+            cases.push_punct(Default::default());
+        }
+
+        quote! {
+            #(#attrs)*
+            macro_rules! #name {
+                (@test_gen_splice [$($test_gen:tt)*] { $($head:tt)* } { $($cases:tt)* }) => {
+                    $($test_gen)*! { $($head)* { $($cases)* #cases } }
+                };
+            }
+        }
+    }
+}
+
+impl Parse for CaseSet {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let name_kw = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected attributes or `name`"))?;
+        let eq = input.parse()?;
+        let name = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected name of case set"))?;
+        let semi = input.parse()?;
+
+        // Mirrors the handling of empty blocks by `test_gen`.
+        input
+            .is_empty()
+            .then(|| Error::new(input.span(), "expected test cases"))
+            .map_or_else(|| input.parse_terminated(TestCase::parse), Result::Err)
+            .map(|cases| Self {
+                attrs,
+                name_kw,
+                eq,
+                name,
+                semi,
+                cases,
+            })
+    }
+}
+
+impl ToTokens for CaseSet {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.name_kw.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.name.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+        self.cases.to_tokens(tokens);
+    }
+}
+
+/// A type representing a separator between the attributes applied to a block of tests,
+/// and the helper "function" used (tuple structs and tuple enum variants are also valid, so this
+/// type allows for those...)
+///
+/// `const fn` helpers are evaluated at compile time, rather than by a test.
+#[derive(Clone)]
+enum Separator {
+    Fn(Token![fn]),
+    ConstFn(Token![const], Token![fn]),
+    Struct(Token![struct]),
+    Enum(Token![enum]),
+}
+
+impl Separator {
+    /// Produces the span of the separator, if it specifies a tuple struct or enum variant,
+    /// which constructs a value, rather than calling a function.
+    fn constructor(&self) -> Option<Span> {
+        match self {
+            Self::Fn(_) | Self::ConstFn(..) => None,
+            Self::Struct(item) => Some(item.span),
+            Self::Enum(item) => Some(item.span),
+        }
+    }
+
+    /// Produces the error for a stream which doesn't begin with a separator.
+    ///
+    /// Other keywords (e.g. `trait` or `pub`) are reported as unrecognised separators,
+    /// whilst a lone path is more likely to be the helper itself, missing its separator.
+    fn error(input: ParseStream) -> Error {
+        let fork = input.fork();
+
+        match fork.call(Ident::parse_any) {
+            Ok(ident) if fork.peek(Ident::peek_any) => Error::new(
+                ident.span(),
+                format!(
+                    "unrecognised separator `{}`, expected `fn`, `const fn`, `struct` or `enum`",
+                    ident
+                ),
+            ),
+            Ok(ident) => Error::new(
+                ident.span(),
+                format!(
+                    "expected `fn`, `const fn`, `struct` or `enum` before the helper, e.g. `fn {}`",
+                    ident
+                ),
+            ),
+            Err(_) => Error::new(
+                input.span(),
+                "expected attributes, `fn`, `const fn`, `struct` or `enum`",
+            ),
+        }
+    }
+}
+
+impl Parse for Separator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![fn]) {
+            input.parse().map(Self::Fn)
+        } else if input.peek(Token![const]) && input.peek2(Token![fn]) {
+            Ok(Self::ConstFn(input.parse()?, input.parse()?))
+        } else if input.peek(Token![struct]) {
+            input.parse().map(Self::Struct)
+        } else if input.peek(Token![enum]) {
+            input.parse().map(Self::Enum)
+        } else {
+            Err(Self::error(input))
+        }
+    }
+}
+
+impl ToTokens for Separator {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Fn(item) => item.to_tokens(tokens),
+            Self::ConstFn(constness, item) => {
+                constness.to_tokens(tokens);
+                item.to_tokens(tokens);
+            }
+            Self::Struct(item) => item.to_tokens(tokens),
+            Self::Enum(item) => item.to_tokens(tokens),
+        }
+    }
+}
+
+/// A type representing an specific test case.
+///
+/// This includes:
+///
+/// * The name it will be assigned
+/// * The separator colon
+/// * The arguments to specify its behaviour (see `CaseArgs` for more detail)
+#[derive(Clone)]
+struct TestCase {
+    fn_name: Ident,
+    colon: Token![:], // Preserved for span
+    args: CaseArgs,
+}
+
+impl TestCase {
+    /// Produces a case, named `fn_name`, passing `args` to the helper function.
+    ///
+    /// Used for cases which aren't parsed from the block itself (e.g. loaded from a data file).
+    fn new(fn_name: Ident, args: FnArgs) -> Self {
+        Self {
+            colon: Default::default(),
+            args: CaseArgs {
+                extends: None,
+                property: None,
+                braces: Brace(args.parens.span),
+                attrs: Vec::new(),
+                body: CaseBody::Args(args),
+                expected: None,
+                return_type: None,
+            },
+            fn_name,
+        }
+    }
+
+    /// Produces the assertion evaluating the case at compile time, for `const fn` blocks.
+    ///
+    /// Panicking in a constant can only produce a string literal at the MSRV of `test_gen`,
+    /// so the message is formatted whilst the macro is expanded, naming the case and its assertion.
+    /// Cases without an expected value assert the helper function returns `true`.
+    fn const_assertion(
+        self,
+        static_attrs: &[Attribute],
+        helper: &Path,
+        static_args: Option<&Punctuated<Expr, Token![,]>>,
+        struct_defaults: Option<&StructDefaults>,
+    ) -> Result<TokenStream2> {
+        let Self {
+            fn_name,
+            args:
+                CaseArgs {
+                    property,
+                    braces,
+                    attrs,
+                    body,
+                    expected,
+                    return_type,
+                    ..
+                },
+            ..
+        } = self;
+
+        if let Some(property) = property {
+            return Err(Error::new_spanned(
+                property,
+                "`const fn` cases are evaluated at compile time, so can't be property cases",
+            ));
+        } else if let Some(return_type) = return_type {
+            return Err(Error::new_spanned(
+                return_type,
+                "`const fn` cases can't specify a return type",
+            ));
+        }
+
+        // Attributes of tests, or interpreted by `test_gen` for tests, don't apply to constants.
+        let test_attrs = [
+            "should_panic",
+            "ignore",
+            "golden",
+            "canonical",
+            "compile_fail",
+            "expect_debug",
+            "budget",
+            "timeout",
+        ];
+
+        if let Some(attr) = static_attrs.iter().chain(&attrs).find(|attr| {
+            test_attrs
+                .iter()
+                .any(|test_attr| attr.path.is_ident(test_attr))
+        }) {
+            return Err(Error::new_spanned(
+                attr,
+                "`const fn` cases are evaluated at compile time, so only apply attributes of items",
+            ));
+        }
+
+        let FnArgs { args, .. } = body.into_fn_args(braces, struct_defaults)?;
+        let call = quote!(#helper(#static_args #args));
+        let helper = helper.to_token_stream().to_string().replace(' ', "");
+        let (assertion, failure) = match expected {
+            Some(Expected { eq, value }) => (
+                quote!(#call #eq #value),
+                "didn't produce the expected value",
+            ),
+            None => (call, "returned `false`"),
+        };
+        let message = format!(
+            "case `{}` failed: `{}` {}",
+            fn_name.unraw(),
+            helper,
+            failure
+        );
+
+        Ok(quote_spanned! {fn_name.span()=>
+            #(#static_attrs)*
+            #(#attrs)*
+            const _: () = ::std::assert!(#assertion, #message);
+        })
+    }
+}
+
+impl Parse for TestCase {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Maps to a bespoke error,
+        // as stating generally than an "Ident" was expected,
+        // isn't particularly helpful...
+        let fn_name = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected test case name"))?;
+        let colon = input.parse()?;
+
+        input.parse().map(|args| Self {
+            fn_name,
+            colon,
+            args,
+        })
+    }
+}
+
+impl ToTokens for TestCase {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.fn_name.to_tokens(tokens);
+        self.colon.to_tokens(tokens);
+        self.args.to_tokens(tokens);
+    }
+}
+
+/// A type representing values defining the behaviour of a test case.
+///
+/// This includes:
+///
+/// * The case it extends, if any
+/// * Whether it's a property case, and how many inputs it's run on
+/// * The surrounding braces
+/// * The attributes to apply to the specific test case
+/// * The arguments to pass to the helper function for the specific test case
+/// * The value the helper function is expected to produce, for cases of `const fn` blocks
+/// * The expected return type for the specific test case
+#[derive(Clone)]
+struct CaseArgs {
+    extends: Option<Extends>,
+    property: Option<Property>,
+    braces: Brace, // Preserved for span
+    attrs: Vec<Attribute>,
+    body: CaseBody,
+    expected: Option<Expected>,
+    return_type: Option<ReturnType>,
+}
+
+impl CaseArgs {
+    /// Produces the arguments of a case extending `parent`,
+    /// replacing the pieces of the parent specified by the case.
+    ///
+    /// Return types replace that of the parent if specified, whilst attributes are appended
+    /// to those of the parent, except those a case can only specify once
+    /// (i.e. `should_panic`, `ignore`, and those interpreted by `test_gen`, such as `budget`),
+    /// which replace any of the same name, and arguments are replaced individually,
+    /// by index, or by field name.
+    fn inherit(self, parent: CaseArgs) -> Result<CaseArgs> {
+        let overrides = match self.body {
+            CaseBody::Overrides(overrides) => overrides,
+            // Only cases with an `extends` clause are parsed with overrides,
+            // and are the only cases which are resolved against a parent.
+            _ => unreachable!("only cases with overrides extend another case"),
+        };
+
+        let body = match parent.body {
+            CaseBody::Args(mut args) => {
+                for ArgOverride { member, value, .. } in overrides {
+                    let index = match &member {
+                        Member::Unnamed(index) => index.index as usize,
+                        Member::Named(_) => return Err(Error::new_spanned(
+                            member,
+                            "overriding arguments by name requires a struct-literal parent case",
+                        )),
+                    };
+                    let len = args.args.len();
+
+                    *args.args.iter_mut().nth(index).ok_or_else(|| {
+                        Error::new_spanned(
+                            &member,
+                            format!("parent case only has {} argument(s)", len),
+                        )
+                    })? = value;
+                }
+
+                CaseBody::Args(args)
+            }
+            CaseBody::Fields(mut fields) => {
+                // Fields which aren't specified by the parent are appended,
+                // as the block's base expression may still be providing them.
+                for ArgOverride { member, value, .. } in overrides {
+                    match fields.iter_mut().find(|field| field.member == member) {
+                        Some(field) => field.expr = value,
+                        None => {
+                            if !fields.empty_or_trailing() {
+                                fields.push_punct(Default::default());
+                            }
+
+                            fields.push(parse_quote!(#member: #value));
+                        }
+                    }
+                }
+
+                CaseBody::Fields(fields)
+            }
+            CaseBody::Overrides(_) => {
+                unreachable!("parent cases are resolved before being extended")
+            }
+        };
+
+        // Attributes which stack, such as `cfg` and `allow`, apply alongside those of the parent.
+        let replaced = |inherited: &Attribute| {
+            ["should_panic", "ignore"]
+                .iter()
+                .chain(&Modes::NAMES)
+                .any(|name| {
+                    inherited.path.is_ident(name)
+                        && self.attrs.iter().any(|attr| attr.path.is_ident(name))
+                })
+        };
+        let mut attrs: Vec<_> = parent
+            .attrs
+            .into_iter()
+            .filter(|inherited| !replaced(inherited))
+            .collect();
+        attrs.extend(self.attrs);
+
+        Ok(CaseArgs {
+            extends: None,
+            property: self.property.or(parent.property),
+            braces: self.braces,
+            attrs,
+            body,
+            expected: self.expected.or(parent.expected),
+            return_type: self.return_type.or(parent.return_type),
+        })
+    }
+}
+
+impl Parse for CaseArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let extends = input.peek(kw::extends).then(|| input.parse()).transpose()?;
+        let property = input
+            .peek(kw::property)
+            .then(|| input.parse())
+            .transpose()?;
+
+        let inner;
+        let braces = braced!(inner in input);
+
+        let attrs = inner.call(Attribute::parse_outer)?;
+
+        // Cases extending another only specify the arguments they replace,
+        // which may be omitted entirely, where only attributes or the return type are replaced.
+        let body = if extends.is_some() {
+            inner
+                .call(parse_before_arrow)
+                .and_then(|overrides| Punctuated::parse_terminated.parse2(overrides))
+                .map(CaseBody::Overrides)?
+        } else {
+            inner.parse()?
+        };
+        let expected = inner.call(Expected::try_parse)?;
+
+        inner.call(ReturnType::try_parse).map(|return_type| Self {
+            extends,
+            property,
+            braces,
+            attrs,
+            body,
+            expected,
+            return_type,
+        })
+    }
+}
+
+impl ToTokens for CaseArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.extends.to_tokens(tokens);
+        self.property.to_tokens(tokens);
+        // All the values for the case are surrounded with braces in this case,
+        // so the tokens are all appended within the body of the closure...
+        self.braces.surround(tokens, |inner| {
+            self.attrs.iter().for_each(|attr| attr.to_tokens(inner));
+            self.body.to_tokens(inner);
+            self.expected.to_tokens(inner);
+            self.return_type.to_tokens(inner);
+        });
+    }
+}
+
+/// A type representing the value a case of a `const fn` block expects the helper function to produce.
+#[derive(Clone)]
+struct Expected {
+    eq: Token![==],
+    value: Expr,
+}
+
+impl Expected {
+    /// Conditionally parses the expected value, if `==` is peeked from the stream.
+    fn try_parse(input: ParseStream) -> Result<Option<Self>> {
+        input.peek(Token![==]).then(|| input.parse()).transpose()
+    }
+}
+
+impl Parse for Expected {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let eq = input.parse()?;
+        let value = input
+            .call(parse_before_arrow)
+            .and_then(syn::parse2)
+            .map_err(|err| Error::new(err.span(), "expected value"))?;
+
+        Ok(Self { eq, value })
+    }
+}
+
+impl ToTokens for Expected {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.eq.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+/// A type representing the `property` clause of a test case,
+/// which makes its arguments the strategies producing the inputs it's run on,
+/// optionally specifying the number of inputs.
+#[derive(Clone)]
+struct Property {
+    property: kw::property,
+    iterations: Option<(Paren, LitInt)>,
+}
+
+impl Property {
+    /// The number of inputs property cases are run on, unless specified otherwise.
+    const DEFAULT_ITERATIONS: u32 = 256;
+
+    fn iterations(&self) -> Result<u32> {
+        self.iterations
+            .as_ref()
+            .map_or(Ok(Self::DEFAULT_ITERATIONS), |(_, iterations)| {
+                iterations.base10_parse()
+            })
+    }
+}
+
+/// Identifies whether a strategy uses a `proptest` strategy, with `proptest(strategy)`,
+/// in which case `proptest` support is emitted for the case.
+fn uses_proptest(tokens: TokenStream2) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "proptest" => {
+                if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+                {
+                    return true;
+                }
+            }
+            TokenTree::Group(group) if uses_proptest(group.stream()) => return true,
+            _ => {}
+        }
+    }
+
+    false
+}
+
+impl Parse for Property {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let property = input.parse()?;
+        let iterations = input
+            .peek(Paren)
+            .then(|| -> Result<_> {
+                let inner;
+                let parens = parenthesized!(inner in input);
+
+                inner
+                    .parse()
+                    .map_err(|err| Error::new(err.span(), "expected number of inputs"))
+                    .map(|iterations| (parens, iterations))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            property,
+            iterations,
+        })
+    }
+}
+
+impl ToTokens for Property {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.property.to_tokens(tokens);
+
+        if let Some((parens, iterations)) = &self.iterations {
+            parens.surround(tokens, |inner| iterations.to_tokens(inner));
+        }
+    }
+}
+
+/// A type representing the `extends` clause of a test case,
+/// naming the case it's derived from.
+#[derive(Clone)]
+struct Extends {
+    extends: kw::extends,
+    parent: Ident,
+}
+
+impl Parse for Extends {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let extends = input.parse()?;
+
+        input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected name of the extended case"))
+            .map(|parent| Self { extends, parent })
+    }
+}
+
+impl ToTokens for Extends {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.extends.to_tokens(tokens);
+        self.parent.to_tokens(tokens);
+    }
+}
+
+/// A type representing the values passed to the helper function for a test case.
+///
+/// These can either be specified as a list of arguments,
+/// or as the fields of a struct literal, which is passed as the only case specific argument,
+/// filling any remaining fields from the block's `with` clause.
+///
+/// Cases extending another case instead specify the arguments they replace.
+#[derive(Clone)]
+enum CaseBody {
+    Args(FnArgs),
+    Fields(Punctuated<FieldValue, Token![,]>),
+    Overrides(Punctuated<ArgOverride, Token![,]>),
+}
+
+impl CaseBody {
+    /// Converts the body into the arguments passed to the helper function.
+    ///
+    /// Struct-literal bodies are wrapped in the struct type specified by `defaults`,
+    /// producing an error spanning `braces`, if no `with` clause was specified for the block.
+    fn into_fn_args(self, braces: Brace, defaults: Option<&StructDefaults>) -> Result<FnArgs> {
+        let fields = match self {
+            Self::Args(args) => return Ok(args),
+            Self::Fields(fields) => fields,
+            Self::Overrides(_) => unreachable!("inheritance is resolved before cases are expanded"),
+        };
+
+        let StructDefaults { path, base, .. } = defaults.ok_or_else(|| {
+            Error::new(
+                braces.span,
+                "struct-literal cases require a `with` clause, specifying the struct type",
+            )
+        })?;
+
+        // The base expression is preceeded by `..`,
+        // which requires a separating comma after the explicitly specified fields.
+        let mut fields = fields;
+
+        if base.is_some() && !fields.empty_or_trailing() {
+            fields.push_punct(Default::default());
+        }
+
+        let base = base.iter().map(|(.., base)| base);
+        let arg: Expr = parse_quote!(#path { #fields #(..#base)* });
+
+        Ok(FnArgs {
+            parens: Paren(braces.span),
+            args: std::iter::once(arg).collect(),
+        })
+    }
+}
+
+impl Parse for CaseBody {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // We can actually usefully validate
+        // we're receiving the next token we expect in this case! Hooray!
+        if input.peek(Paren) {
+            return input.parse().map(Self::Args);
+        }
+
+        if !((input.peek(Ident) || input.peek(LitInt)) && input.peek2(Token![:])) {
+            return Err(Error::new(
+                input.span(),
+                "expected attributes, function parameters or struct fields",
+            ));
+        }
+
+        input
+            .call(parse_before_arrow)
+            .and_then(|fields| Punctuated::parse_terminated.parse2(fields))
+            .map(Self::Fields)
+    }
+}
+
+impl ToTokens for CaseBody {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Args(args) => args.to_tokens(tokens),
+            Self::Fields(fields) => fields.to_tokens(tokens),
+            Self::Overrides(overrides) => overrides.to_tokens(tokens),
+        }
+    }
+}
+
+/// Splits off the tokens preceeding any return type from the stream.
+///
+/// Unlike function parameters, struct fields and overrides aren't delimited by a group,
+/// and an expression parser will happily treat the `-` of a return type's arrow
+/// as subtraction... Instead, the tokens preceeding any return type are split off,
+/// so they can be parsed separately.
+///
+/// Arrows within the fields themselves (e.g. `f: |x| -> u8 { x }`, or `f: g as fn(u8) -> u8`)
+/// belong to a closure or function type, rather than the case, so are skipped.
+fn parse_before_arrow(input: ParseStream) -> Result<TokenStream2> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        let mut tokens = Vec::new();
+
+        while let Some((tt, next)) = rest.token_tree() {
+            if matches!(&tt, TokenTree::Punct(punct) if punct.as_char() == '-' && punct.spacing() == Spacing::Joint)
+                && matches!(next.punct(), Some((punct, _)) if punct.as_char() == '>')
+                && !returns_within(&tokens)
+            {
+                break;
+            }
+
+            tokens.push(tt);
+            rest = next;
+        }
+
+        Ok((tokens.into_iter().collect(), rest))
+    })
+}
+
+/// Identifies whether an arrow following `tokens` specifies the return type
+/// of a closure (`|x| ->`), or a function type (`fn(u8) ->`, `Fn(u8) ->`), within them.
+fn returns_within(tokens: &[TokenTree]) -> bool {
+    match tokens {
+        [.., TokenTree::Punct(punct)] => punct.as_char() == '|',
+        [.., TokenTree::Ident(ident), TokenTree::Group(group)]
+            if group.delimiter() == Delimiter::Parenthesis =>
+        {
+            ident == "fn" || ident == "Fn" || ident == "FnMut" || ident == "FnOnce"
+        }
+        _ => false,
+    }
+}
+
+/// A type representing an argument replaced by a case extending another,
+/// either by its index, or the name of a struct-literal field.
+#[derive(Clone)]
+struct ArgOverride {
+    member: Member,
+    eq: Token![=],
+    value: Expr,
+}
+
+impl Parse for ArgOverride {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let member = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected argument index or field name"))?;
+        let eq = input.parse()?;
+
+        input.parse().map(|value| Self { member, eq, value })
+    }
+}
+
+impl ToTokens for ArgOverride {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.member.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.value.to_tokens(tokens);
+    }
+}
+
+/// A type representing a list of arguments, and the parentheise around them.
+#[derive(Clone)]
+struct FnArgs {
+    parens: Paren, // Preserved for span
+    args: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for FnArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let inner;
+        let parens = parenthesized!(inner in input);
+
+        // Using `punctuated::parse_separated_nonempty` in this case,
+        // because if this type is being parsed, arguments should be expected,
+        // and trailing commas look sloppy...
+        inner
+            .call(Punctuated::parse_separated_nonempty)
+            .map_err(|err| Error::new(err.span(), "expected function arguments"))
+            .map(|args| Self { parens, args })
+    }
+}
+
+impl ToTokens for FnArgs {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.parens
+            .surround(tokens, |inner| self.args.to_tokens(inner))
+    }
+}
+
+/// A type representing a round-trip block, where the helper function encodes the value of each case,
+/// and the decoding function decodes it again.
+///
+/// The `roundtrip` keyword precedes the separator of the helper function,
+/// so is appended separately, with the remaining tokens following the helper function.
+#[derive(Clone)]
+struct Roundtrip {
+    roundtrip: kw::roundtrip,
+    comma: Token![,],
+    separator: Separator, // Preserved for span
+    decode: Path,
+}
 
-        // NOTE: Whilst designed with the intent of specifying a helper *function*,
-        // an oddity of the syntax for `test_gen`, means it's also compatible
-        // with tuple structs and enum varients! Oop! xD
-        let helper = input
+impl Roundtrip {
+    /// Parses the decoding function, following the helper function of a round-trip block.
+    fn parse_decoder(roundtrip: kw::roundtrip, input: ParseStream) -> Result<Self> {
+        let comma = input
             .parse()
-            .map_err(|err| Error::new(err.span(), "expected helper function"))?;
-
-        // Parsing is only attempted, if parenthese are peeked,
-        // as this would indicate the user wishes to specify static args.
-        let static_args = input.peek(Paren).then(|| input.parse()).transpose()?;
-        let static_return_type = input.call(ReturnType::try_parse)?;
-        let farrow = input.parse()?;
-        let cases;
-        let braces = braced!(cases in input);
+            .map_err(|err| Error::new(err.span(), "expected `,` and decoding function"))?;
+        let separator = input.parse()?;
+        let decode = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected decoding function"))?;
 
-        // If the contents of `cases` is empty,
-        // `ParseBuffer::parse_terminated` will simply produce an empty
-        // `Punctuated` struct, and no error. On the other hand,
-        // `Punctuated::parse_separated_nonempty` falls back on the parsing error
-        // for `TestCase`, as well as not allow trailing commas,
-        // neither of which is particularly helpful in this case...
-        //
-        // Instead, its explicitly checked whether `cases` is empty,
-        // resulting in a bespoke error which provides an explanation which is actually
-        // helpful.
-        cases
-            .is_empty()
-            .then(|| Error::new(cases.span(), "expected test cases"))
-            .map_or_else(|| cases.parse_terminated(TestCase::parse), Result::Err)
-            .map(|cases| Self {
-                static_attrs,
-                separator,
-                helper,
-                static_args,
-                static_return_type,
-                farrow,
-                braces,
-                cases,
-            })
+        Ok(Self {
+            roundtrip,
+            comma,
+            separator,
+            decode,
+        })
     }
 }
 
-impl ToTokens for MacroHelper {
+impl ToTokens for Roundtrip {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        // `ToTokens` isn't implemented for anything like `&[T: ToTokens]`
-        // or `(Into)Iterator<Item: ToTokens>`, so appending them iteratively
-        // is about as clean a solution as possible...
-        self.static_attrs
-            .iter()
-            .for_each(|attr| attr.to_tokens(tokens));
+        self.comma.to_tokens(tokens);
         self.separator.to_tokens(tokens);
-        self.helper.to_tokens(tokens);
-        self.static_args.to_tokens(tokens);
-        self.static_return_type.to_tokens(tokens);
-        self.farrow.to_tokens(tokens);
-        // Token groups are kind of weird, so uses `surround` to identify the tokens which the
-        // group should surround...
-        self.braces
-            .surround(tokens, |inner| self.cases.to_tokens(inner));
+        self.decode.to_tokens(tokens);
     }
 }
 
-/// A type representing a separator between the attributes applied to a block of tests,
-/// and the helper "function" used (tuple structs and tuple enum variants are also valid, so this
-/// type allows for those...)
+/// A type representing the reference implementation of a block,
+/// which every case also calls, comparing its output with that of the helper function.
 #[derive(Clone)]
-enum Separator {
-    Fn(Token![fn]),
-    Struct(Token![struct]),
-    Enum(Token![enum]),
+struct Reference {
+    vs: kw::vs,
+    path: Path,
 }
 
-impl Parse for Separator {
+impl Reference {
+    /// Conditionally parses the reference, if the `vs` keyword is peeked from the stream.
+    fn try_parse(input: ParseStream) -> Result<Option<Self>> {
+        input.peek(kw::vs).then(|| input.parse()).transpose()
+    }
+}
+
+impl Parse for Reference {
     fn parse(input: ParseStream) -> Result<Self> {
-        let lookahead = input.lookahead1();
+        let vs = input.parse()?;
 
-        if lookahead.peek(Token![fn]) {
-            input.parse().map(Self::Fn)
-        } else if lookahead.peek(Token![struct]) {
-            input.parse().map(Self::Struct)
-        } else if lookahead.peek(Token![enum]) {
-            input.parse().map(Self::Enum)
-        } else {
-            Err(lookahead.error())
-        }
+        input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected reference implementation"))
+            .map(|path| Self { vs, path })
     }
 }
 
-impl ToTokens for Separator {
+impl ToTokens for Reference {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        match self {
-            Self::Fn(item) => item.to_tokens(tokens),
-            Self::Struct(item) => item.to_tokens(tokens),
-            Self::Enum(item) => item.to_tokens(tokens),
-        }
+        self.vs.to_tokens(tokens);
+        self.path.to_tokens(tokens);
     }
 }
 
-/// A type representing an specific test case.
-///
-/// This includes:
-///
-/// * The name it will be assigned
-/// * The separator colon
-/// * The arguments to specify its behaviour (see `CaseArgs` for more detail)
+/// A type representing the `with` clause of a block,
+/// specifying the struct type constructed by struct-literal cases,
+/// and optionally, the base expression any unspecified fields are taken from.
 #[derive(Clone)]
-struct TestCase {
-    fn_name: Ident,
-    colon: Token![:], // Preserved for span
-    args: CaseArgs,
+struct StructDefaults {
+    with: kw::with,
+    path: Path,
+    base: Option<(Brace, Token![..], Expr)>,
 }
 
-impl Parse for TestCase {
+impl StructDefaults {
+    /// Conditionally parses the clause, if the `with` keyword is peeked from the stream.
+    fn try_parse(input: ParseStream) -> Result<Option<Self>> {
+        input.peek(kw::with).then(|| input.parse()).transpose()
+    }
+}
+
+impl Parse for StructDefaults {
     fn parse(input: ParseStream) -> Result<Self> {
-        // Maps to a bespoke error,
-        // as stating generally than an "Ident" was expected,
-        // isn't particularly helpful...
-        let fn_name = input
+        let with = input.parse()?;
+        let path = input
             .parse()
-            .map_err(|err| Error::new(err.span(), "expected test case name"))?;
-        let colon = input.parse()?;
+            .map_err(|err| Error::new(err.span(), "expected struct type"))?;
 
-        input.parse().map(|args| Self {
-            fn_name,
-            colon,
-            args,
-        })
+        // Base expressions are specified using the same syntax as a struct literal,
+        // which only specifies the functional update syntax, e.g. `Case { ..BASE }`.
+        let base = input
+            .peek(Brace)
+            .then(|| -> Result<_> {
+                let inner;
+                let braces = braced!(inner in input);
+
+                Ok((braces, inner.parse()?, inner.parse()?))
+            })
+            .transpose()?;
+
+        Ok(Self { with, path, base })
     }
 }
 
-impl ToTokens for TestCase {
+impl ToTokens for StructDefaults {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        self.fn_name.to_tokens(tokens);
-        self.colon.to_tokens(tokens);
-        self.args.to_tokens(tokens);
+        self.with.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+
+        if let Some((braces, dot2, base)) = &self.base {
+            braces.surround(tokens, |inner| {
+                dot2.to_tokens(inner);
+                base.to_tokens(inner);
+            });
+        }
     }
 }
 
-/// A type representing values defining the behaviour of a test case.
-///
-/// This includes:
-///
-/// * The surrounding braces
-/// * The attributes to apply to the specific test case
-/// * The arguments to pass to the helper function for the specific test case
-/// * The expected return type for the specific test case
+/// A type representing the `exhaustive over` clause of a block,
+/// specifying an enum which every variant of must be passed to the helper function by some case.
 #[derive(Clone)]
-struct CaseArgs {
-    braces: Brace, // Preserved for span
-    attrs: Vec<Attribute>,
-    args: FnArgs,
-    return_type: Option<ReturnType>,
+struct Exhaustive {
+    exhaustive: kw::exhaustive,
+    over: kw::over,
+    ty: Path,
 }
 
-impl Parse for CaseArgs {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let inner;
-        let braces = braced!(inner in input);
+impl Exhaustive {
+    /// Conditionally parses the clause, if the `exhaustive` keyword is peeked from the stream.
+    fn try_parse(input: ParseStream) -> Result<Option<Self>> {
+        input
+            .peek(kw::exhaustive)
+            .then(|| input.parse())
+            .transpose()
+    }
 
-        let attrs = inner.call(Attribute::parse_outer)?;
+    /// Produces the pattern matching the variant of the enum constructed by `arg`, if any.
+    ///
+    /// Only variants specified by a path through the enum (e.g. `Fruit::Apple`) are matched,
+    /// with any fields of the variant being ignored by the pattern.
+    ///
+    /// Calls of tuple variants and associated functions (e.g. `Fruit::new(..)`) can't be told apart
+    /// by their syntax alone, so only calls of paths ending in an uppercase name, as variants
+    /// conventionally are, are matched, with calls of any others being ignored.
+    fn pattern(&self, arg: &Expr) -> Option<TokenStream2> {
+        let (path, fields) = match arg {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) => (path, quote!()),
+            Expr::Call(ExprCall { func, .. }) => match &**func {
+                Expr::Path(ExprPath {
+                    qself: None, path, ..
+                }) if path.segments.last().into_iter().any(|segment| {
+                    segment
+                        .ident
+                        .unraw()
+                        .to_string()
+                        .starts_with(char::is_uppercase)
+                }) =>
+                {
+                    (path, quote!((..)))
+                }
+                _ => return None,
+            },
+            Expr::Struct(ExprStruct { path, .. }) => (path, quote!({ .. })),
+            _ => return None,
+        };
 
-        // We can actually usefully validate
-        // we're receiving the next token we expect in this case! Hooray!
-        if !inner.peek(Paren) {
-            return Err(Error::new(
-                inner.span(),
-                "expected attributes or function parameters",
-            ));
+        // Enums and their variants may be specified by different paths (e.g. `self::Fruit`),
+        // so only the name of the enum preceding the variant is compared.
+        let name = self.ty.segments.last().map(|segment| &segment.ident);
+        let parent = path.segments.iter().rev().nth(1);
+
+        (parent.map(|segment| &segment.ident) == name).then(|| quote!(#path #fields))
+    }
+
+    /// Produces an item matching the variants passed to the helper function by `cases`,
+    /// which fails to compile, naming any variants which aren't.
+    ///
+    /// Any `cfg` attributes of the block are also applied to the item,
+    /// as the enum may only be available where they're enabled.
+    fn check(&self, attrs: &[Attribute], cases: &[TestCase]) -> TokenStream2 {
+        let Self { exhaustive, ty, .. } = self;
+        let cfgs = attrs.iter().filter(|attr| attr.path.is_ident("cfg"));
+        let patterns = cases
+            .iter()
+            // Property cases specify strategies, rather than values.
+            .filter(|case| case.args.property.is_none())
+            .flat_map(|case| match &case.args.body {
+                CaseBody::Args(args) => args.args.iter().collect(),
+                CaseBody::Fields(fields) => fields.iter().map(|field| &field.expr).collect(),
+                CaseBody::Overrides(_) => Vec::new(),
+            })
+            .filter_map(|arg| self.pattern(arg));
+        let value = quote_spanned!(exhaustive.span=> value);
+
+        quote! {
+            #(#cfgs)*
+            const _: () = {
+                #[allow(dead_code, unreachable_patterns)]
+                fn exhaustive(#value: &#ty) {
+                    match #value {
+                        #(#patterns => {})*
+                    }
+                }
+            };
         }
+    }
+}
 
-        let args = inner.parse()?;
+impl Parse for Exhaustive {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let exhaustive = input.parse()?;
+        let over = input.parse()?;
+        let ty = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected enum type"))?;
 
-        inner.call(ReturnType::try_parse).map(|return_type| Self {
-            braces,
-            attrs,
-            args,
-            return_type,
+        Ok(Self {
+            exhaustive,
+            over,
+            ty,
         })
     }
 }
 
-impl ToTokens for CaseArgs {
+impl ToTokens for Exhaustive {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        // All the values for the case are surrounded with braces in this case,
-        // so the tokens are all appended within the body of the closure...
-        self.braces.surround(tokens, |inner| {
-            self.attrs.iter().for_each(|attr| attr.to_tokens(inner));
-            self.args.to_tokens(inner);
-            self.return_type.to_tokens(inner);
-        });
+        self.exhaustive.to_tokens(tokens);
+        self.over.to_tokens(tokens);
+        self.ty.to_tokens(tokens);
     }
 }
 
-/// A type representing a list of arguments, and the parentheise around them.
+/// A type representing the `check = path` clause of a `struct` or `enum` block,
+/// specifying a function which checks an invariant of every value constructed by the block.
 #[derive(Clone)]
-struct FnArgs {
-    parens: Paren, // Preserved for span
-    args: Punctuated<Expr, Token![,]>,
+struct Invariant {
+    check: kw::check,
+    eq: Token![=],
+    path: Path,
 }
 
-impl Parse for FnArgs {
+impl Invariant {
+    /// Conditionally parses the clause, if the `check` keyword is peeked from the stream.
+    fn try_parse(input: ParseStream) -> Result<Option<Self>> {
+        input.peek(kw::check).then(|| input.parse()).transpose()
+    }
+}
+
+impl Parse for Invariant {
     fn parse(input: ParseStream) -> Result<Self> {
-        let inner;
-        let parens = parenthesized!(inner in input);
+        let check = input.parse()?;
+        let eq = input.parse()?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected invariant function"))?;
 
-        // Using `punctuated::parse_separated_nonempty` in this case,
-        // because if this type is being parsed, arguments should be expected,
-        // and trailing commas look sloppy...
-        inner
-            .call(Punctuated::parse_separated_nonempty)
-            .map_err(|err| Error::new(err.span(), "expected function arguments"))
-            .map(|args| Self { parens, args })
+        Ok(Self { check, eq, path })
     }
 }
 
-impl ToTokens for FnArgs {
+impl ToTokens for Invariant {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        self.parens
-            .surround(tokens, |inner| self.args.to_tokens(inner))
+        self.check.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.path.to_tokens(tokens);
     }
 }
 
-/// A type representing the right-arrow and function's return type signature.
+/// A type representing the right-arrow and function's return type signature,
+/// or the snapshot the returned value is compared with instead.
 #[derive(Clone)]
 struct ReturnType {
     arrow: Token![->],
-    return_type: Type,
+    return_type: ReturnKind,
 }
 
 impl ReturnType {
@@ -458,25 +3198,116 @@ impl ReturnType {
     fn try_parse(input: ParseStream) -> Result<Option<Self>> {
         input.peek(Token![->]).then(|| input.parse()).transpose()
     }
+
+    /// Produces the type returned by the test, unless a snapshot is compared with instead.
+    fn ty(&self) -> Option<&Type> {
+        match &self.return_type {
+            ReturnKind::Type(return_type) => Some(return_type),
+            ReturnKind::Snapshot(_) => None,
+        }
+    }
+
+    /// Produces the snapshot the returned value is compared with, if it is one.
+    fn snapshot(&self) -> Option<&Snapshot> {
+        match &self.return_type {
+            ReturnKind::Snapshot(snapshot) => Some(snapshot),
+            ReturnKind::Type(_) => None,
+        }
+    }
 }
 
 impl Parse for ReturnType {
     fn parse(input: ParseStream) -> Result<Self> {
         let arrow = input.parse()?;
 
+        // `snapshot` could also be parsed as a type,
+        // so is checked for before the return type.
+        if input.peek(kw::snapshot) && !input.peek2(Token![::]) && !input.peek2(Token![<]) {
+            return input.parse().map(|snapshot| Self {
+                arrow,
+                return_type: ReturnKind::Snapshot(snapshot),
+            });
+        }
+
         // Default error message is a bit obtuse in this case,
         // so it's mapped to a more specific bespoke error instead.
         input
             .parse()
             .map_err(|err| Error::new(err.span(), "expected a return type"))
-            .map(|return_type| Self { arrow, return_type })
+            .map(|return_type| Self {
+                arrow,
+                return_type: ReturnKind::Type(return_type),
+            })
     }
 }
 
 impl ToTokens for ReturnType {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         self.arrow.to_tokens(tokens);
-        self.return_type.to_tokens(tokens);
+
+        match &self.return_type {
+            ReturnKind::Type(return_type) => return_type.to_tokens(tokens),
+            ReturnKind::Snapshot(snapshot) => snapshot.to_tokens(tokens),
+        }
+    }
+}
+
+/// A type representing what a case returns.
+#[derive(Clone)]
+enum ReturnKind {
+    Type(Box<Type>),
+    Snapshot(Snapshot),
+}
+
+/// A type representing a snapshot, which the value returned by a case is compared with,
+/// rather than being returned by the test.
+///
+/// Values are formatted with `Debug` (pretty-printed), unless `Display` is specified.
+#[derive(Clone)]
+struct Snapshot {
+    snapshot: kw::snapshot,
+    format: Option<(Paren, Ident)>,
+}
+
+impl Snapshot {
+    /// Produces an expression formatting `value` for comparison with the snapshot.
+    fn format(&self, value: TokenStream2) -> TokenStream2 {
+        match &self.format {
+            Some((_, format)) if format == "Display" => quote!(::std::format!("{}", #value)),
+            _ => quote!(::std::format!("{:#?}", #value)),
+        }
+    }
+}
+
+impl Parse for Snapshot {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let snapshot = input.parse()?;
+        let format = input
+            .peek(Paren)
+            .then(|| -> Result<_> {
+                let content;
+                let parens = parenthesized!(content in input);
+                let format = content.parse::<Ident>()?;
+
+                if format != "Debug" && format != "Display" {
+                    return Err(Error::new_spanned(format, "expected `Debug` or `Display`"));
+                }
+
+                Ok((parens, format))
+            })
+            .transpose()?;
+
+        Ok(Self { snapshot, format })
+    }
+}
+
+impl ToTokens for Snapshot {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.snapshot.to_tokens(tokens);
+
+        if let Some((parens, format)) = &self.format {
+            parens.surround(tokens, |inner| format.to_tokens(inner));
+        }
     }
 }
 
@@ -493,7 +3324,7 @@ mod tests {
         // because the relevant `Display` implementation, doesn't account for formatting of the parsed source...
         let tokens: TokenStream2 = p.parse().expect("string could not be parsed as tokens");
 
-        let parsed = syn::parse_str::<P>(&p)
+        let parsed = syn::parse_str::<P>(p)
             .expect("tokens could not be parsed as type")
             .into_token_stream()
             .to_string();
@@ -504,6 +3335,9 @@ mod tests {
     #[test]
     fn return_type_parsing() {
         parse_to_tokens::<ReturnType>("-> usize");
+        parse_to_tokens::<ReturnType>("-> snapshot");
+        parse_to_tokens::<ReturnType>("-> snapshot(Display)");
+        parse_to_tokens::<ReturnType>("-> snapshot::Type");
     }
 
     #[test]
@@ -516,13 +3350,123 @@ mod tests {
         parse_to_tokens::<CaseArgs>("{ #[ignore] (1, 2) -> usize }");
     }
 
+    #[test]
+    fn case_args_struct_parsing() {
+        parse_to_tokens::<CaseArgs>("{ #[ignore] input: \"x\", expected: 3 -> usize }");
+    }
+
+    #[test]
+    fn case_args_property_parsing() {
+        parse_to_tokens::<CaseArgs>("property { (0..10, just(1)) }");
+        parse_to_tokens::<CaseArgs>("extends parent property(1000) { 0 = 0..=5 }");
+    }
+
+    #[test]
+    fn case_args_extends_parsing() {
+        parse_to_tokens::<CaseArgs>(
+            "extends base { #[ignore] 1 = vec![0; 1 << 20], name = 2 -> usize }",
+        );
+    }
+
+    #[test]
+    fn struct_defaults_parsing() {
+        parse_to_tokens::<StructDefaults>("with Case { ..BASE }");
+    }
+
     #[test]
     fn test_case_parsing() {
         parse_to_tokens::<TestCase>("test: { #[ignore] (1, 2) -> usize }");
     }
 
+    #[test]
+    fn case_set_parsing() {
+        parse_to_tokens::<CaseSet>(
+            "#[macro_export] name = set; test: { #[ignore] (1, 2) -> usize }",
+        );
+    }
+
+    #[test]
+    fn suite_parsing() {
+        parse_to_tokens::<suites::Suite>(
+            "#[macro_export] name = suite; fn check, #[ignore] fn self::check as renamed -> Result<(), String>",
+        );
+        parse_to_tokens::<suites::Instantiation>(
+            "#[ignore] instantiate suite suite for [u8, #[cfg(unix)] Vec<u8> as bytes];",
+        );
+    }
+
+    #[test]
+    fn assert_impl_parsing() {
+        parse_to_tokens::<types::AssertImpl>(
+            "#[cfg(unix)] assert_impl { Send + Sync + 'static } for [u8, #[cfg(unix)] Vec<u8> as bytes]",
+        );
+        parse_to_tokens::<types::AssertImpl>("assert_impl { Iterator<Item = u8> } for [str];");
+    }
+
+    #[test]
+    fn test_helper_sources_parsing() {
+        parse_to_tokens::<MacroHelper>("fn Into::into => { use cases set; use cases other::set; }");
+        parse_to_tokens::<MacroHelper>("fn Into::into => { for each variant of Color; for each variant of super::Fruit; own: { (1) } }");
+    }
+
+    #[test]
+    fn test_helper_data_parsing() {
+        parse_to_tokens::<MacroHelper>("fn Into::into => { cases from csv \"a.csv\"; cases from tsv \"b.tsv\"; cases from json \"c.json\" as Case; cases from toml \"d.toml\"; cases from glob \"e/*.txt\" as str; cases from golden \"f/*.in\"; regressions \"g.txt\"; cases: { (1) } }");
+        parse_to_tokens::<MacroHelper>("fn Into::into => { regressions: { (1) } }");
+        parse_to_tokens::<MacroHelper>(
+            "fn Into::into exhaustive over Fruit => { apple: { (Fruit::Apple) } }",
+        );
+        parse_to_tokens::<MacroHelper>("fn Into::into with Case { ..BASE } exhaustive over crate::Fruit<u8> => { exhaustive: { (1) } }");
+    }
+
     #[test]
     fn test_helper_parsing() {
         parse_to_tokens::<MacroHelper>("#[should_panic] fn Into::into -> (usize, usize) => { test: { #[ignore] (1, 2) -> usize } }");
+        parse_to_tokens::<MacroHelper>(
+            "fn fast::parse vs reference::parse (1) -> snapshot => { test: { (2) } }",
+        );
+        parse_to_tokens::<MacroHelper>(
+            "#[generate(tests, benches)] fn parse -> usize => { small: { (\"1\") } }",
+        );
+        parse_to_tokens::<MacroHelper>(
+            "#[budget(ms = 5)] fn parse => { small: { #[budget(us = 50, iterations = 1000)] (\"1\") } }",
+        );
+        parse_to_tokens::<MacroHelper>(
+            "#[timeout(secs = 2)] fn parse -> Result<(), ()> => { slow: { #[timeout(ms = 10)] (\"1\") } }",
+        );
+        parse_to_tokens::<MacroHelper>(
+            "const fn mean (0) => { equal: { (4, 4) == 4 }, extended: extends equal { 1 = 2 } }",
+        );
+        parse_to_tokens::<MacroHelper>(
+            "struct Range::new (0) -> Range check = Range::validate with Bounds => { test: { #[expect_debug(\"Range\")] (1) } }",
+        );
+    }
+
+    #[test]
+    fn case_calls() {
+        let expand = |helper: &str| {
+            syn::parse_str::<MacroHelper>(helper)
+                .unwrap()
+                .restructure()
+                .map(|tokens| tokens.to_string())
+                .map_err(|err| err.to_string())
+        };
+
+        // Benchmarks and budgets of round-trip cases call the encoding function with a reference
+        assert!(expand(
+            "#[generate(tests, benches)] #[budget(ms = 5)] roundtrip fn encode, fn decode => { a: { (1) } }"
+        )
+        .unwrap()
+        .matches("encode (& 1)")
+        .count()
+            == 2);
+        assert!(expand(
+            "#[budget(ms = 5)] roundtrip fn encode, fn decode => { a: { #[canonical] (\"1\") } }"
+        )
+        .unwrap_err()
+        .starts_with("canonical cases call the helper function with a decoded value"));
+        assert!(expand("fn parse => { a: { #[expect_debug(\"1\")] (1) } }")
+            .unwrap_err()
+            .starts_with("only cases of `struct` and `enum` blocks"));
     }
 }
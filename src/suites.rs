@@ -0,0 +1,293 @@
+//! Suites of generic checks, defined once by `test_gen_suite`,
+//! and instantiated for any number of types by `test_gen`.
+//!
+//! Like case sets, suites are carried by a `macro_rules!` macro, which produces a module
+//! for every type the suite is instantiated for, containing a test for every check.
+use crate::{
+    kw,
+    types::{self, TypeCase, TypeCases},
+};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Error, Ident, Path, Result, Token, Type,
+};
+
+/// The type representing the structure of `test_gen_suite`.
+///
+/// This includes:
+///
+/// * The attributes to apply to the produced macro
+/// * The name of the suite
+/// * The checks of the suite
+pub(crate) struct Suite {
+    attrs: Vec<Attribute>,
+    name_kw: kw::name,
+    eq: Token![=],
+    name: Ident,
+    semi: Token![;],
+    checks: Punctuated<Check, Token![,]>,
+}
+
+impl Suite {
+    /// Produces the macro carrying the checks of the suite.
+    ///
+    /// The macro only has a single rule, which produces a module for every type it's passed,
+    /// alongside the name of the module, and the attributes to apply to the module and its tests.
+    pub(crate) fn restructure(self) -> TokenStream2 {
+        let Self {
+            attrs,
+            name,
+            checks,
+            ..
+        } = self;
+        let checks = checks.iter().map(Check::to_test);
+
+        quote! {
+            #(#attrs)*
+            macro_rules! #name {
+                (@test_gen_instantiate $({
+                    [$($module_attrs:tt)*] $module:ident [$($ty:tt)*] [$($attrs:tt)*]
+                })*) => {
+                    $(
+                        $($module_attrs)*
+                        mod $module {
+                            #[allow(unused_imports)]
+                            use super::*;
+
+                            #(#checks)*
+                        }
+                    )*
+                };
+            }
+        }
+    }
+}
+
+impl Parse for Suite {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let name_kw = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected attributes or `name`"))?;
+        let eq = input.parse()?;
+        let name = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected name of suite"))?;
+        let semi = input.parse()?;
+
+        // Mirrors the handling of empty blocks by `test_gen`.
+        input
+            .is_empty()
+            .then(|| Error::new(input.span(), "expected checks"))
+            .map_or_else(|| input.parse_terminated(Check::parse), Result::Err)
+            .map(|checks| Self {
+                attrs,
+                name_kw,
+                eq,
+                name,
+                semi,
+                checks,
+            })
+    }
+}
+
+impl ToTokens for Suite {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.name_kw.to_tokens(tokens);
+        self.eq.to_tokens(tokens);
+        self.name.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+        self.checks.to_tokens(tokens);
+    }
+}
+
+/// A type representing a check of a suite, a generic function called with each type.
+///
+/// Checks are named after the function, unless renamed with `as`.
+struct Check {
+    attrs: Vec<Attribute>,
+    fn_token: Token![fn],
+    path: Path,
+    rename: Option<(Token![as], Ident)>,
+    return_type: Option<(Token![->], Type)>,
+}
+
+impl Check {
+    /// Produces the test of the check, within the module of a type.
+    ///
+    /// Tokens of the macro's rule (e.g. `$($ty)*`) are interpolated into the test,
+    /// as it's only produced when the suite is instantiated.
+    fn to_test(&self) -> TokenStream2 {
+        let Self {
+            attrs,
+            path,
+            rename,
+            return_type,
+            ..
+        } = self;
+        let name = rename.as_ref().map(|(_, name)| name).unwrap_or_else(|| {
+            &path
+                .segments
+                .last()
+                .expect("paths have at least one segment")
+                .ident
+        });
+        let path = relative_to_module(path);
+        let return_type = return_type.as_ref().map(|(arrow, ty)| quote!(#arrow #ty));
+
+        quote! {
+            $($attrs)*
+            #(#attrs)*
+            #[test]
+            fn #name() #return_type {
+                #path::<$($ty)*>()
+            }
+        }
+    }
+}
+
+/// Produces the path of a check, from within the module of a type.
+///
+/// The tests of a suite are named after its checks, so would shadow the check itself
+/// within the module. Relative paths, including those starting with `self` or `super`,
+/// are instead resolved from the parent module, where the suite is instantiated,
+/// whilst absolute paths are used as they are.
+fn relative_to_module(path: &Path) -> TokenStream2 {
+    let first = &path.segments[0].ident;
+
+    if path.leading_colon.is_some() || first == "crate" {
+        return path.to_token_stream();
+    }
+
+    if first == "self" {
+        let rest = path.segments.iter().skip(1);
+        return quote!(super #(::#rest)*);
+    }
+
+    quote!(super::#path)
+}
+
+impl Parse for Check {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let fn_token = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected attributes or `fn`"))?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected check function"))?;
+        let rename = input.call(types::parse_rename)?;
+        let return_type = input
+            .peek(Token![->])
+            .then(|| Ok::<_, Error>((input.parse()?, input.parse()?)))
+            .transpose()?;
+
+        Ok(Self {
+            attrs,
+            fn_token,
+            path,
+            rename,
+            return_type,
+        })
+    }
+}
+
+impl ToTokens for Check {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.fn_token.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+
+        if let Some((as_token, name)) = &self.rename {
+            as_token.to_tokens(tokens);
+            name.to_tokens(tokens);
+        }
+
+        if let Some((arrow, ty)) = &self.return_type {
+            arrow.to_tokens(tokens);
+            ty.to_tokens(tokens);
+        }
+    }
+}
+
+/// A type representing the instantiation of a suite by `test_gen`,
+/// for each of a list of types.
+pub(crate) struct Instantiation {
+    attrs: Vec<Attribute>,
+    instantiate: kw::instantiate,
+    suite: kw::suite,
+    path: Path,
+    for_token: Token![for],
+    types: TypeCases,
+    semi: Option<Token![;]>,
+}
+
+impl Instantiation {
+    /// Identifies whether the stream begins with an instantiation,
+    /// following any attributes.
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+
+        fork.call(Attribute::parse_outer).is_ok() && fork.peek(kw::instantiate)
+    }
+
+    /// Produces an invocation of the suite, passing each type,
+    /// alongside the attributes to apply to its tests.
+    pub(crate) fn restructure(self) -> Result<TokenStream2> {
+        let Self {
+            attrs, path, types, ..
+        } = self;
+        let types = types.named()?.into_iter().map(|(module, case)| {
+            let TypeCase {
+                attrs: module_attrs,
+                ty,
+                ..
+            } = case;
+
+            quote!({ [#(#module_attrs)*] #module [#ty] [#(#attrs)*] })
+        });
+
+        Ok(quote! {
+            #path! {
+                @test_gen_instantiate #(#types)*
+            }
+        })
+    }
+}
+
+impl Parse for Instantiation {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let instantiate = input.parse()?;
+        let suite = input.parse()?;
+        let path = input
+            .parse()
+            .map_err(|err| Error::new(err.span(), "expected name of suite"))?;
+
+        Ok(Self {
+            attrs,
+            instantiate,
+            suite,
+            path,
+            for_token: input.parse()?,
+            types: input.parse()?,
+            semi: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Instantiation {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.instantiate.to_tokens(tokens);
+        self.suite.to_tokens(tokens);
+        self.path.to_tokens(tokens);
+        self.for_token.to_tokens(tokens);
+        self.types.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+    }
+}
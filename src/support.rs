@@ -0,0 +1,273 @@
+//! Runtime support for generated tests, emitted alongside the tests which require it.
+//!
+//! As `test_gen` is a procedural macro crate, it can't provide items to the tests it produces,
+//! so support code is instead emitted as modules, once for each block with tests requiring it,
+//! which those tests import as `__test_gen`.
+//! The modules are written as ordinary source files, which are also compiled as part of this
+//! crate's own tests, so they're checked and tested like the rest of the crate.
+//!
+//! Support modules may refer to each other through `super`, but otherwise only to `std`,
+//! and must compile with the MSRV of `test_gen`, from the 2018 edition onwards.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+#[cfg(test)]
+#[allow(dead_code)]
+mod bless;
+#[cfg(test)]
+#[allow(dead_code)]
+mod budget;
+#[cfg(test)]
+mod diff;
+#[cfg(test)]
+#[allow(dead_code)]
+mod golden;
+#[cfg(test)]
+#[allow(dead_code)]
+mod invariant;
+#[cfg(test)]
+#[allow(dead_code)]
+mod property;
+#[cfg(test)]
+#[allow(dead_code)]
+mod proptest;
+#[cfg(test)]
+#[allow(dead_code)]
+mod roundtrip;
+#[cfg(test)]
+#[allow(dead_code)]
+mod snapshot;
+#[cfg(test)]
+#[allow(dead_code)]
+mod timeout;
+
+/// A runtime support module.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Module {
+    Bless,
+    Budget,
+    Diff,
+    Golden,
+    Invariant,
+    Property,
+    Proptest,
+    Roundtrip,
+    Snapshot,
+    Timeout,
+}
+
+impl Module {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bless => "bless",
+            Self::Budget => "budget",
+            Self::Diff => "diff",
+            Self::Golden => "golden",
+            Self::Invariant => "invariant",
+            Self::Property => "property",
+            Self::Proptest => "proptest",
+            Self::Roundtrip => "roundtrip",
+            Self::Snapshot => "snapshot",
+            Self::Timeout => "timeout",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::Bless => include_str!("support/bless.rs"),
+            Self::Budget => include_str!("support/budget.rs"),
+            Self::Diff => include_str!("support/diff.rs"),
+            Self::Golden => include_str!("support/golden.rs"),
+            Self::Invariant => include_str!("support/invariant.rs"),
+            Self::Property => include_str!("support/property.rs"),
+            Self::Proptest => include_str!("support/proptest.rs"),
+            Self::Roundtrip => include_str!("support/roundtrip.rs"),
+            Self::Snapshot => include_str!("support/snapshot.rs"),
+            Self::Timeout => include_str!("support/timeout.rs"),
+        }
+    }
+
+    /// The modules this module refers to.
+    fn dependencies(self) -> &'static [Self] {
+        match self {
+            Self::Bless
+            | Self::Budget
+            | Self::Diff
+            | Self::Invariant
+            | Self::Property
+            | Self::Roundtrip
+            | Self::Timeout => &[],
+            Self::Proptest => &[Self::Property],
+            Self::Golden | Self::Snapshot => &[Self::Bless, Self::Diff],
+        }
+    }
+}
+
+/// Produces the support module `name` of a block, containing the support `modules`,
+/// and their dependencies.
+///
+/// The module is only compiled alongside the tests referring to it, with `cfg(test)`.
+pub(crate) fn emit(name: &Ident, modules: &[Module]) -> TokenStream2 {
+    let mut included = Vec::new();
+    let mut pending = modules.to_vec();
+
+    while let Some(module) = pending.pop() {
+        if !included.contains(&module) {
+            included.push(module);
+            pending.extend(module.dependencies());
+        }
+    }
+
+    let modules = included.into_iter().map(|module| {
+        let name = format_ident!("{}", module.name());
+        let source = module
+            .source()
+            .parse::<TokenStream2>()
+            .expect("support modules should be valid Rust");
+
+        quote!(pub mod #name { #source })
+    });
+
+    quote! {
+        #[cfg(test)]
+        #[allow(dead_code)]
+        mod #name {
+            #(#modules)*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn diff_lines() {
+        assert_eq!(diff::lines("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            diff::lines("a\nb\nc", "a\nx\nc").as_deref(),
+            Some("  a\n- b\n+ x\n  c\n")
+        );
+        assert_eq!(
+            diff::lines("1\n2\n3\n4\n5\n6\n7", "1\n2\n3\n4\n5\n6\n8").as_deref(),
+            Some("   ...\n  5\n  6\n- 7\n+ 8\n")
+        );
+        assert_eq!(diff::lines("a", "a\n").as_deref(), Some("  a\n+ \n"));
+        assert_eq!(
+            diff::lines("1\n2\n3\n4\n5\n6\n7", "1\n2\n8\n4\n5\n6\n7").as_deref(),
+            Some("  1\n  2\n- 3\n+ 8\n  4\n  5\n   ...\n")
+        );
+
+        // Outputs too large to diff only report their first difference
+        let expected = (0..2000).map(|line| line.to_string()).collect::<Vec<_>>();
+        let actual = (0..2000)
+            .map(|line| (line * 2).to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            diff::lines(&expected.join("\n"), &actual.join("\n")).as_deref(),
+            Some("   ... (too many lines to diff, first difference on line 2)\n- 1\n+ 2\n   ...\n")
+        );
+    }
+
+    #[test]
+    fn modules_emitting() {
+        let emitted = emit(&format_ident!("__test_gen_case"), &[Module::Golden]).to_string();
+
+        assert!(emitted.contains("pub mod golden"));
+        assert!(emitted.contains("pub mod diff"));
+        assert!(!emitted.contains("pub mod snapshot"));
+    }
+
+    #[test]
+    fn budget_summaries() {
+        let times = (1..=100).map(Duration::from_millis).collect::<Vec<_>>();
+
+        assert_eq!(
+            budget::summary(&times),
+            (
+                Duration::from_millis(1),
+                Duration::from_millis(51),
+                Duration::from_millis(95)
+            )
+        );
+        assert_eq!(budget::summary(&times[..1]), (times[0], times[0], times[0]));
+        assert_eq!(budget::black_box(String::from("a")), "a");
+    }
+
+    #[test]
+    fn snapshot_paths() {
+        assert!(snapshot::path("tests::nested", "case")
+            .ends_with("tests/snapshots/tests__nested__case.snap"));
+    }
+
+    #[test]
+    fn property_strategies() {
+        use property::{any, vec_of, Rng, Strategy};
+
+        let mut rng = Rng::new(property::seed("property_strategies"));
+
+        for _ in 0..1000 {
+            let value: i8 = (-3..=3).generate(&mut rng);
+            assert!((-3..=3).contains(&value));
+
+            let value: u64 = (10..u64::MAX).generate(&mut rng);
+            assert!(value >= 10);
+
+            let value: f32 = (0.5..1.0).generate(&mut rng);
+            assert!((0.5..1.0).contains(&value));
+
+            let value: char = ('\u{d000}'..='\u{e000}').generate(&mut rng);
+            assert!(('\u{d000}'..='\u{e000}').contains(&value));
+
+            let items: Vec<u8> = vec_of(any::<u8>(), 2..4).generate(&mut rng);
+            assert!((2..4).contains(&items.len()));
+        }
+
+        let edges = (0..1000)
+            .map(|_| any::<u32>().generate(&mut rng))
+            .filter(|value| [0, 1, u32::MAX].contains(value))
+            .count();
+        assert!(edges > 0);
+    }
+
+    #[test]
+    fn property_shrinking() {
+        use property::{any, just, vec_of, Args, Strategy};
+
+        assert_eq!((-10..=10).shrink(&8), [0, 4, 6, 7]);
+        assert_eq!((-10..=-2).shrink(&-8), [-2, -5, -7]);
+        assert!(any::<u8>().shrink(&0).is_empty());
+        assert_eq!((0.5..2.0).shrink(&1.5), [0.5, 1.0]);
+        assert_eq!(any::<bool>().shrink(&true), [false]);
+        assert_eq!(
+            any::<String>().shrink(&String::from("abc")),
+            ["", "bc", "ac", "ab"]
+        );
+
+        let items = vec_of(0..10_u8, 1..=3);
+        assert_eq!(
+            items.shrink(&vec![1, 2]),
+            [vec![2], vec![1], vec![0, 2], vec![1, 0], vec![1, 1]]
+        );
+
+        let args = (just(1), any::<String>(), vec_of(0.0..1.0_f32, 0..2));
+        assert_eq!(
+            args.args(&(1, String::from("a"), vec![f32::NAN])),
+            r#"(1, String::from("a"), vec![f32::NAN])"#
+        );
+        assert_eq!((just('a'),).source(&('a',)), "('a',)");
+    }
+
+    #[test]
+    fn property_seeds() {
+        assert_eq!(property::seed(""), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(property::seed("a"), property::seed("b"));
+        assert_eq!(
+            property::Rng::new(0).next_u64(),
+            property::Rng::new(0).next_u64()
+        );
+    }
+}
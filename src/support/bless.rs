@@ -0,0 +1,22 @@
+//! Blessing, which rewrites the expected outputs of tests with their actual outputs.
+use std::{env, ffi::OsStr, fs, path::Path};
+
+/// The environment variable which, when set to `1`,
+/// causes expected outputs to be rewritten, rather than compared.
+pub const BLESS: &str = "TEST_GEN_BLESS";
+
+/// Whether expected outputs should be rewritten, rather than compared.
+pub fn blessing() -> bool {
+    env::var_os(BLESS).as_deref() == Some(OsStr::new("1"))
+}
+
+/// Writes `contents` to the file at `path`, creating any missing directories.
+pub fn write(path: &Path, contents: &str) {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .unwrap_or_else(|err| panic!("couldn't create `{}`: {}", parent.display(), err));
+    }
+
+    fs::write(path, contents)
+        .unwrap_or_else(|err| panic!("couldn't write `{}`: {}", path.display(), err));
+}
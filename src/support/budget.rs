@@ -0,0 +1,46 @@
+//! Measurement of the time taken by a case, compared with its performance budget.
+//!
+//! The helper function is run repeatedly, and the median time taken is compared with the budget,
+//! as it's less affected than the mean by occasional outliers (e.g. the thread being descheduled).
+use std::time::{Duration, Instant};
+
+/// Runs `run` `iterations` times, panicking if the median time taken exceeds `budget`.
+pub fn check<F: FnMut()>(name: &str, budget: Duration, iterations: usize, mut run: F) {
+    let mut times = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .collect::<Vec<_>>();
+    times.sort();
+
+    let (min, median, p95) = summary(&times);
+
+    if median > budget {
+        panic!(
+            "case `{}` exceeded its budget of {:?}, over {} iteration(s):\n\n   min: {:?}\nmedian: {:?}\n   p95: {:?}\n",
+            name, budget, iterations, min, median, p95
+        );
+    }
+}
+
+/// Produces the minimum, median and 95th percentile of the sorted `times`.
+pub fn summary(times: &[Duration]) -> (Duration, Duration, Duration) {
+    // The smallest time which at least `percent`% of the times don't exceed.
+    let percentile = |percent: usize| times[(times.len() * percent - 1) / 100];
+
+    (times[0], times[times.len() / 2], percentile(95))
+}
+
+/// Prevents the compiler from optimising away `value`, or the computation producing it.
+///
+/// `std::hint::black_box` is only available from Rust 1.66, so a volatile read is used instead.
+pub fn black_box<T>(value: T) -> T {
+    // SAFETY: `value` is read from a valid reference, and forgotten, so isn't dropped twice.
+    unsafe {
+        let copy = std::ptr::read_volatile(&value);
+        std::mem::forget(value);
+        copy
+    }
+}
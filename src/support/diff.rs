@@ -0,0 +1,143 @@
+//! Line diffs, reported when the output of a test doesn't match its expected output.
+
+/// Produces a diff of the lines of `expected` and `actual`, or `None` if they're equal.
+///
+/// Lines only found in `expected` are prefixed by `-`, lines only found in `actual` by `+`,
+/// and lines common to both by a space. Only lines near a change are included.
+pub fn lines(expected: &str, actual: &str) -> Option<String> {
+    /// The number of unchanged lines included around each change.
+    const CONTEXT: usize = 2;
+    /// The most entries of the table of common subsequences diffing the lines.
+    const MAX_TABLE: usize = 1 << 20;
+
+    if expected == actual {
+        return None;
+    }
+
+    // Outputs usually end with a newline, which would otherwise be reported as an empty line.
+    let (expected, actual) = match (expected.strip_suffix('\n'), actual.strip_suffix('\n')) {
+        (Some(expected), Some(actual)) => (expected, actual),
+        _ => (expected, actual),
+    };
+
+    let expected = expected.split('\n').collect::<Vec<_>>();
+    let actual = actual.split('\n').collect::<Vec<_>>();
+
+    // Lines common to the start and end of both are unchanged, so only the lines between them
+    // are diffed by the table below, keeping it small where outputs only change in places.
+    let prefix = expected
+        .iter()
+        .zip(&actual)
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(expected, actual)| expected == actual)
+        .count();
+    let (common, middle_expected, middle_actual) = (
+        &expected[..prefix],
+        &expected[prefix..expected.len() - suffix],
+        &actual[prefix..actual.len() - suffix],
+    );
+
+    // The table takes time and memory proportional to the product of the numbers of lines it diffs,
+    // so beyond `MAX_TABLE` entries, only the first differing lines are reported instead.
+    let width = middle_actual.len() + 1;
+
+    if (middle_expected.len() + 1).saturating_mul(width) > MAX_TABLE {
+        let mut diff = format!(
+            "   ... (too many lines to diff, first difference on line {})\n",
+            prefix + 1
+        );
+
+        for (tag, line) in [('-', middle_expected.first()), ('+', middle_actual.first())] {
+            if let Some(line) = line {
+                diff.push(tag);
+                diff.push(' ');
+                diff.push_str(line);
+                diff.push('\n');
+            }
+        }
+
+        diff.push_str("   ...\n");
+
+        return Some(diff);
+    }
+
+    // The lengths of the longest common subsequences of every pair of suffixes,
+    // which the diff is then produced from, by walking both sets of lines from the start.
+    let mut lengths = vec![0_usize; (middle_expected.len() + 1) * width];
+
+    for i in (0..middle_expected.len()).rev() {
+        for j in (0..middle_actual.len()).rev() {
+            lengths[i * width + j] = if middle_expected[i] == middle_actual[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(expected.len() + actual.len());
+    let (mut i, mut j) = (0, 0);
+
+    edits.extend(common.iter().map(|line| (' ', *line)));
+
+    while i < middle_expected.len() || j < middle_actual.len() {
+        if i < middle_expected.len()
+            && j < middle_actual.len()
+            && middle_expected[i] == middle_actual[j]
+        {
+            edits.push((' ', middle_expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == middle_actual.len()
+            || (i < middle_expected.len()
+                && lengths[(i + 1) * width + j] >= lengths[i * width + j + 1])
+        {
+            edits.push(('-', middle_expected[i]));
+            i += 1;
+        } else {
+            edits.push(('+', middle_actual[j]));
+            j += 1;
+        }
+    }
+
+    edits.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|line| (' ', *line)),
+    );
+
+    let mut diff = String::new();
+    let mut skipped = false;
+
+    for (index, (tag, line)) in edits.iter().enumerate() {
+        let near_change = edits
+            [index.saturating_sub(CONTEXT)..(index + CONTEXT + 1).min(edits.len())]
+            .iter()
+            .any(|(tag, _)| *tag != ' ');
+
+        if near_change {
+            if skipped {
+                diff.push_str("   ...\n");
+                skipped = false;
+            }
+
+            diff.push(*tag);
+            diff.push(' ');
+            diff.push_str(line);
+            diff.push('\n');
+        } else {
+            skipped = true;
+        }
+    }
+
+    if skipped {
+        diff.push_str("   ...\n");
+    }
+
+    Some(diff)
+}
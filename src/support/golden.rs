@@ -0,0 +1,36 @@
+//! Comparison of the output of a test with its expected output, stored in a golden file.
+use super::{bless, diff};
+use std::{fmt::Display, fs, path::Path};
+
+/// Compares `actual` with the contents of the golden file at `path`,
+/// panicking with a line diff if they differ.
+///
+/// When blessing, the file is instead rewritten with `actual`.
+pub fn check(path: &str, actual: impl Display) {
+    let actual = actual.to_string();
+
+    if bless::blessing() {
+        bless::write(Path::new(path), &actual);
+        return;
+    }
+
+    // Checkouts may convert line endings, which shouldn't be reported as a difference.
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(err) => panic!(
+            "couldn't read `{}`: {}\n\nrerun with `{}=1` to create it",
+            path,
+            err,
+            bless::BLESS
+        ),
+    };
+
+    if let Some(diff) = diff::lines(&expected, &actual) {
+        panic!(
+            "output doesn't match `{}`:\n\n{}\nrerun with `{}=1` to update it",
+            path,
+            diff,
+            bless::BLESS
+        );
+    }
+}
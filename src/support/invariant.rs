@@ -0,0 +1,51 @@
+//! Checks of the values constructed by the cases of `struct` and `enum` blocks.
+//!
+//! Invariants are checked by a function taking the value by reference, which may panic,
+//! or return a `bool` or `Result`, in the same way as the outcome of a property case.
+use std::fmt::Debug;
+
+/// The value returned by an invariant function.
+pub trait Outcome {
+    /// Produces a description of the violation, if the invariant doesn't hold.
+    fn violation(self) -> Option<String>;
+}
+
+impl Outcome for () {
+    fn violation(self) -> Option<String> {
+        None
+    }
+}
+
+impl Outcome for bool {
+    fn violation(self) -> Option<String> {
+        (!self).then(|| String::from("returned `false`"))
+    }
+}
+
+impl<T, E: Debug> Outcome for Result<T, E> {
+    fn violation(self) -> Option<String> {
+        self.err().map(|err| format!("returned `Err({:?})`", err))
+    }
+}
+
+/// Checks the outcome of the invariant `invariant` for the case `name`.
+pub fn check<O: Outcome>(name: &str, invariant: &str, outcome: O) {
+    if let Some(violation) = outcome.violation() {
+        panic!(
+            "case `{}` violated the invariant `{}`, which {}",
+            name, invariant, violation
+        );
+    }
+}
+
+/// Compares the `Debug` output of the value constructed by the case `name` with `expected`.
+pub fn expect_debug<T: Debug>(name: &str, expected: &str, value: &T) {
+    let actual = format!("{:?}", value);
+
+    if actual != expected {
+        panic!(
+            "case `{}` didn't produce the expected `Debug` output:\n\nexpected: {}\n  actual: {}\n",
+            name, expected, actual
+        );
+    }
+}
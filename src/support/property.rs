@@ -0,0 +1,669 @@
+//! Property cases, which run a test over a number of deterministic, pseudo-random inputs.
+//!
+//! Inputs are produced by strategies, from a generator seeded per input. The seed of the first
+//! input is derived from the name of the case (or `TEST_GEN_SEED`), so failing inputs can be
+//! reproduced by running the case with the seed reported for them.
+//!
+//! Failing inputs are then shrunk, by the strategy which produced them, to the simplest input
+//! still failing, which is reported as the source of a case reproducing it.
+use std::{
+    any::Any as Payload,
+    cell::Cell,
+    env,
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Range, RangeInclusive},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// The environment variable overriding the seed of the first input of every property case.
+pub const SEED: &str = "TEST_GEN_SEED";
+
+/// A SplitMix64 pseudo-random number generator.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Produces a value below `bound`, which may be up to `2^64`.
+    pub fn below(&mut self, bound: u128) -> u128 {
+        match bound {
+            0 => 0,
+            _ if bound > u128::from(u64::MAX) => u128::from(self.next_u64()),
+            _ => (u128::from(self.next_u64()) * bound) >> 64,
+        }
+    }
+
+    /// Whether an edge value should be produced, rather than one chosen uniformly.
+    fn edge(&mut self) -> bool {
+        self.next_u64() & 7 == 0
+    }
+
+    /// Produces a value within `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+/// A means of producing inputs of a property case.
+///
+/// The type of input is a parameter of the trait, rather than an associated type,
+/// so the types of range bounds (e.g. integer literals) can be inferred from the helper function.
+pub trait Strategy<T> {
+    fn generate(&self, rng: &mut Rng) -> T;
+
+    /// Produces values simpler than `value`, which the strategy could have produced,
+    /// ordered from the simplest.
+    fn shrink(&self, _: &T) -> Vec<T> {
+        Vec::new()
+    }
+
+    /// Produces the Rust source of `value`.
+    fn source(&self, value: &T) -> String
+    where
+        T: Debug,
+    {
+        format!("{:?}", value)
+    }
+}
+
+/// Produces integers between `value` and `target`, from `target` itself,
+/// halving the distance from `value` until reaching its neighbour.
+fn shrink_int(value: i128, target: i128) -> Vec<i128> {
+    let mut candidates = Vec::new();
+    let mut distance = value - target;
+
+    while distance != 0 {
+        candidates.push(value - distance);
+        distance /= 2;
+    }
+
+    candidates
+}
+
+/// Produces items with chunks of `items` removed, leaving each of the given `lengths`.
+fn shrink_len<T: Clone>(items: &[T], lengths: impl IntoIterator<Item = usize>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    for len in lengths {
+        let removed = items.len() - len;
+
+        for start in (0..=len).step_by(removed) {
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[start + removed..]);
+            candidates.push(candidate);
+        }
+    }
+
+    candidates
+}
+
+/// Integers are produced uniformly, with the bounds of the range being produced more often.
+macro_rules! int_strategies {
+    ($($ty:ty),*) => {$(
+        impl Strategy<$ty> for Range<$ty> {
+            fn generate(&self, rng: &mut Rng) -> $ty {
+                assert!(self.start < self.end, "can't generate values from an empty range");
+                (self.start..=self.end - 1).generate(rng)
+            }
+
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                (self.start..=self.end - 1).shrink(value)
+            }
+        }
+
+        impl Strategy<$ty> for RangeInclusive<$ty> {
+            fn generate(&self, rng: &mut Rng) -> $ty {
+                let (start, end) = (*self.start(), *self.end());
+                assert!(start <= end, "can't generate values from an empty range");
+
+                if rng.edge() {
+                    return if rng.next_u64() & 1 == 0 { start } else { end };
+                }
+
+                let span = (end as i128 - start as i128) as u128 + 1;
+                (start as i128 + rng.below(span) as i128) as $ty
+            }
+
+            /// Values are shrunk towards zero, or the bound of the range nearest it.
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                let target = (0 as $ty).max(*self.start()).min(*self.end());
+
+                shrink_int(*value as i128, target as i128)
+                    .into_iter()
+                    .map(|value| value as $ty)
+                    .collect()
+            }
+        }
+
+        impl Arbitrary for $ty {
+            fn arbitrary(rng: &mut Rng) -> $ty {
+                // Zero and one are edges of any integer type, alongside its bounds.
+                if rng.edge() {
+                    let edges = [<$ty>::MIN, <$ty>::MAX, 0, 1, <$ty>::MIN / 2 + <$ty>::MAX / 2];
+                    return edges[rng.below(edges.len() as u128) as usize];
+                }
+
+                (<$ty>::MIN..=<$ty>::MAX).generate(rng)
+            }
+
+            fn shrink(&self) -> Vec<$ty> {
+                (<$ty>::MIN..=<$ty>::MAX).shrink(self)
+            }
+        }
+    )*};
+}
+
+int_strategies!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+macro_rules! float_strategies {
+    ($($ty:ty),*) => {$(
+        impl Strategy<$ty> for Range<$ty> {
+            fn generate(&self, rng: &mut Rng) -> $ty {
+                assert!(self.start < self.end, "can't generate values from an empty range");
+
+                if rng.edge() {
+                    return self.start;
+                }
+
+                let value = self.start + (rng.unit() as $ty) * (self.end - self.start);
+                // Rounding may produce the excluded end of the range.
+                if value < self.end { value } else { self.start }
+            }
+
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                shrink_float(*value, self.start, self.end)
+                    .into_iter()
+                    .filter(|value| *value < self.end)
+                    .collect()
+            }
+
+            fn source(&self, value: &$ty) -> String {
+                float_source(*value, stringify!($ty))
+            }
+        }
+
+        impl Strategy<$ty> for RangeInclusive<$ty> {
+            fn generate(&self, rng: &mut Rng) -> $ty {
+                let (start, end) = (*self.start(), *self.end());
+                assert!(start <= end, "can't generate values from an empty range");
+
+                if rng.edge() {
+                    return if rng.next_u64() & 1 == 0 { start } else { end };
+                }
+
+                (start + (rng.unit() as $ty) * (end - start)).min(end)
+            }
+
+            fn shrink(&self, value: &$ty) -> Vec<$ty> {
+                shrink_float(*value, *self.start(), *self.end())
+            }
+
+            fn source(&self, value: &$ty) -> String {
+                float_source(*value, stringify!($ty))
+            }
+        }
+
+        impl FloatShrink for $ty {
+            fn shrink(self, start: Self, end: Self) -> Vec<Self> {
+                let target = (0.0 as $ty).max(start).min(end);
+                let mut candidates = vec![target, self.trunc()];
+
+                candidates.retain(|candidate| {
+                    *candidate != self && (start..=end).contains(candidate)
+                });
+                candidates.dedup();
+                candidates
+            }
+        }
+    )*};
+}
+
+/// Floats are shrunk towards zero, or the bound of the range nearest it,
+/// and otherwise towards the integer they're nearest.
+trait FloatShrink: Sized {
+    fn shrink(self, start: Self, end: Self) -> Vec<Self>;
+}
+
+fn shrink_float<F: FloatShrink>(value: F, start: F, end: F) -> Vec<F> {
+    value.shrink(start, end)
+}
+
+/// Produces the source of a float, which may not be finite.
+fn float_source<F: Debug>(value: F, ty: &str) -> String {
+    match format!("{:?}", value).as_str() {
+        "NaN" => format!("{}::NAN", ty),
+        "inf" => format!("{}::INFINITY", ty),
+        "-inf" => format!("{}::NEG_INFINITY", ty),
+        source => source.to_owned(),
+    }
+}
+
+float_strategies!(f32, f64);
+
+impl Strategy<char> for Range<char> {
+    fn generate(&self, rng: &mut Rng) -> char {
+        assert!(
+            self.start < self.end,
+            "can't generate values from an empty range"
+        );
+        (self.start..=char::from_u32(self.end as u32 - 1).unwrap_or('\u{d7ff}')).generate(rng)
+    }
+}
+
+impl Strategy<char> for RangeInclusive<char> {
+    fn generate(&self, rng: &mut Rng) -> char {
+        let range = *self.start() as u32..=*self.end() as u32;
+
+        // Surrogates aren't valid characters, so are skipped.
+        loop {
+            if let Some(c) = char::from_u32(Strategy::<u32>::generate(&range, rng)) {
+                return c;
+            }
+        }
+    }
+
+    fn shrink(&self, value: &char) -> Vec<char> {
+        (value != self.start())
+            .then_some(*self.start())
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Types which any value of can be produced, by `any`.
+pub trait Arbitrary: Debug + Sized {
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    /// Produces values simpler than the value, ordered from the simplest.
+    fn shrink(&self) -> Vec<Self>;
+
+    /// Produces the Rust source of the value.
+    fn source(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> bool {
+        rng.next_u64() & 1 == 0
+    }
+
+    fn shrink(&self) -> Vec<bool> {
+        self.then_some(false).into_iter().collect()
+    }
+}
+
+impl Arbitrary for char {
+    fn arbitrary(rng: &mut Rng) -> char {
+        // ASCII is produced more often, as it's more likely to be meaningful to the test.
+        if rng.next_u64() & 1 == 0 {
+            ('\0'..='\x7f').generate(rng)
+        } else {
+            ('\0'..=char::MAX).generate(rng)
+        }
+    }
+
+    fn shrink(&self) -> Vec<char> {
+        (*self != 'a').then_some('a').into_iter().collect()
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng) -> String {
+        let len = (0..=32_usize).generate(rng);
+        (0..len).map(|_| char::arbitrary(rng)).collect()
+    }
+
+    /// Strings are shrunk by dropping characters.
+    fn shrink(&self) -> Vec<String> {
+        let chars = self.chars().collect::<Vec<_>>();
+        let lengths = (0..=chars.len()).shrink(&chars.len());
+
+        shrink_len(&chars, lengths)
+            .into_iter()
+            .map(|chars| chars.into_iter().collect())
+            .collect()
+    }
+
+    fn source(&self) -> String {
+        format!("String::from({:?})", self)
+    }
+}
+
+/// A strategy producing any value of `T`, produced by `any`.
+pub struct Any<T>(PhantomData<T>);
+
+impl<T: Arbitrary> Strategy<T> for Any<T> {
+    fn generate(&self, rng: &mut Rng) -> T {
+        T::arbitrary(rng)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+
+    fn source(&self, value: &T) -> String {
+        value.source()
+    }
+}
+
+/// Produces any value of `T`.
+pub fn any<T: Arbitrary>() -> Any<T> {
+    Any(PhantomData)
+}
+
+/// A strategy always producing the same value, produced by `just`.
+pub struct Just<T>(T);
+
+impl<T: Clone> Strategy<T> for Just<T> {
+    fn generate(&self, _: &mut Rng) -> T {
+        self.0.clone()
+    }
+}
+
+/// Always produces `value`.
+pub fn just<T: Clone>(value: T) -> Just<T> {
+    Just(value)
+}
+
+/// A strategy producing `Vec`s, produced by `vec_of`.
+pub struct VecOf<S, L>(S, L);
+
+impl<T: Clone + Debug, S: Strategy<T>, L: Strategy<usize>> Strategy<Vec<T>> for VecOf<S, L> {
+    fn generate(&self, rng: &mut Rng) -> Vec<T> {
+        let len = self.1.generate(rng);
+        (0..len).map(|_| self.0.generate(rng)).collect()
+    }
+
+    /// `Vec`s are shrunk by removing elements, to the lengths the length could be shrunk to,
+    /// and then by shrinking their elements.
+    fn shrink(&self, value: &Vec<T>) -> Vec<Vec<T>> {
+        let mut candidates = shrink_len(value, self.1.shrink(&value.len()));
+
+        for (index, element) in value.iter().enumerate() {
+            for element in self.0.shrink(element) {
+                let mut candidate = value.clone();
+                candidate[index] = element;
+                candidates.push(candidate);
+            }
+        }
+
+        candidates
+    }
+
+    fn source(&self, value: &Vec<T>) -> String {
+        let elements = value
+            .iter()
+            .map(|element| self.0.source(element))
+            .collect::<Vec<_>>();
+
+        format!("vec![{}]", elements.join(", "))
+    }
+}
+
+/// Produces `Vec`s of values produced by `element`, with a length produced by `len`.
+pub fn vec_of<S, L: Strategy<usize>>(element: S, len: L) -> VecOf<S, L> {
+    VecOf(element, len)
+}
+
+/// Strategies producing the arguments of a property case.
+pub trait Args<T>: Strategy<T> {
+    /// Produces the source of the arguments within `value`, as they're specified by a case.
+    fn args(&self, value: &T) -> String;
+}
+
+/// Tuples are shrunk by shrinking each of their values, in order.
+macro_rules! tuple_strategies {
+    ($(($($name:ident $value:ident $index:tt),*))*) => {$(
+        impl<$($name, $value),*> Strategy<($($value,)*)> for ($($name,)*)
+        where
+            $($name: Strategy<$value>, $value: Clone + Debug,)*
+        {
+            fn generate(&self, rng: &mut Rng) -> ($($value,)*) {
+                ($(self.$index.generate(rng),)*)
+            }
+
+            fn shrink(&self, value: &($($value,)*)) -> Vec<($($value,)*)> {
+                let mut candidates = Vec::new();
+
+                $(
+                    for shrunk in self.$index.shrink(&value.$index) {
+                        let mut candidate = value.clone();
+                        candidate.$index = shrunk;
+                        candidates.push(candidate);
+                    }
+                )*
+
+                candidates
+            }
+
+            fn source(&self, value: &($($value,)*)) -> String {
+                match &[$(self.$index.source(&value.$index)),*][..] {
+                    [value] => format!("({},)", value),
+                    values => format!("({})", values.join(", ")),
+                }
+            }
+        }
+
+        impl<$($name, $value),*> Args<($($value,)*)> for ($($name,)*)
+        where
+            $($name: Strategy<$value>, $value: Clone + Debug,)*
+        {
+            fn args(&self, value: &($($value,)*)) -> String {
+                let args: &[String] = &[$(self.$index.source(&value.$index)),*];
+                format!("({})", args.join(", "))
+            }
+        }
+    )*};
+}
+
+impl Strategy<()> for () {
+    fn generate(&self, _: &mut Rng) {}
+}
+
+impl Args<()> for () {
+    fn args(&self, _: &()) -> String {
+        String::from("()")
+    }
+}
+
+tuple_strategies! {
+    (A Ta 0)
+    (A Ta 0, B Tb 1)
+    (A Ta 0, B Tb 1, C Tc 2)
+    (A Ta 0, B Tb 1, C Tc 2, D Td 3)
+    (A Ta 0, B Tb 1, C Tc 2, D Td 3, E Te 4)
+    (A Ta 0, B Tb 1, C Tc 2, D Td 3, E Te 4, F Tf 5)
+    (A Ta 0, B Tb 1, C Tc 2, D Td 3, E Te 4, F Tf 5, G Tg 6)
+    (A Ta 0, B Tb 1, C Tc 2, D Td 3, E Te 4, F Tf 5, G Tg 6, H Th 7)
+}
+
+/// The outcome of running a property case on an input.
+pub trait Outcome {
+    /// Produces a description of the failure, if the case failed.
+    fn failure(self) -> Option<String>;
+}
+
+impl Outcome for () {
+    fn failure(self) -> Option<String> {
+        None
+    }
+}
+
+impl Outcome for bool {
+    fn failure(self) -> Option<String> {
+        (!self).then(|| String::from("returned `false`"))
+    }
+}
+
+impl<T, E: Debug> Outcome for Result<T, E> {
+    fn failure(self) -> Option<String> {
+        self.err().map(|err| format!("returned `Err({:?})`", err))
+    }
+}
+
+/// Produces the seed of the first input of the case `name`.
+pub fn seed(name: &str) -> u64 {
+    if let Ok(seed) = env::var(SEED) {
+        let parsed = match seed.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => seed.parse(),
+        };
+
+        return parsed.unwrap_or_else(|err| panic!("`{}` isn't a valid seed: {}", SEED, err));
+    }
+
+    // FNV-1a, as the seed must be stable between compilations.
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Produces a description of a panic, from its payload.
+fn panic_message(payload: &(dyn Payload + Send)) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("non-string payload");
+
+    format!("panicked with `{}`", message)
+}
+
+/// Runs `test` on `input`, producing a description of its failure, if it fails.
+fn run<I, O: Outcome>(test: &impl Fn(I) -> O, input: I) -> Option<String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| test(input))) {
+        Ok(outcome) => outcome.failure(),
+        Err(payload) => Some(panic_message(&*payload)),
+    }
+}
+
+thread_local! {
+    /// Whether panics on the current thread are silenced.
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with the output of panics on the current thread silenced,
+/// as shrinking an input may cause the case to panic many times.
+///
+/// The panic hook is shared between threads (i.e. other tests), so it's only replaced once,
+/// with a hook which defers to the previous hook where panics on the thread aren't silenced.
+fn silenced<R>(f: impl FnOnce() -> R) -> R {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+
+    SILENCED.with(|silenced| silenced.set(true));
+    let result = f();
+    SILENCED.with(|silenced| silenced.set(false));
+
+    result
+}
+
+/// The maximum number of inputs run whilst shrinking a failing input.
+const SHRINK_RUNS: usize = 4096;
+
+/// Shrinks the failing `input` to the simplest input still failing,
+/// producing it alongside its failure, and the number of times it was shrunk.
+fn shrink<S, I, O>(
+    strategy: &S,
+    test: &impl Fn(I) -> O,
+    mut input: I,
+    mut failure: String,
+) -> (I, String, usize)
+where
+    S: Strategy<I>,
+    I: Clone,
+    O: Outcome,
+{
+    let mut runs = 0;
+    let mut steps = 0;
+
+    'shrinking: while runs < SHRINK_RUNS {
+        for candidate in strategy.shrink(&input) {
+            if runs == SHRINK_RUNS {
+                break 'shrinking;
+            }
+
+            runs += 1;
+
+            if let Some(candidate_failure) = run(test, candidate.clone()) {
+                input = candidate;
+                failure = candidate_failure;
+                steps += 1;
+                continue 'shrinking;
+            }
+        }
+
+        break;
+    }
+
+    (input, failure, steps)
+}
+
+/// Runs `test` on `iterations` inputs produced by `strategy`, panicking with the first
+/// failing input, the seed it was produced from, and the simplest input it could be shrunk to.
+pub fn check<S, I, O>(name: &str, iterations: u32, strategy: S, test: impl Fn(I) -> O)
+where
+    S: Args<I>,
+    I: Clone + Debug,
+    O: Outcome,
+{
+    let mut seed = seed(name);
+
+    for iteration in 1..=iterations {
+        let input = strategy.generate(&mut Rng::new(seed));
+
+        if let Some(failure) = run(&test, input.clone()) {
+            let (shrunk, shrunk_failure, steps) =
+                silenced(|| shrink(&strategy, &test, input.clone(), failure.clone()));
+            let shrunk = strategy.args(&shrunk);
+
+            panic!(
+                "property case `{}` failed on input {} of {}, which {}:\n\ninput: {}\nseed: {:#x}\n\n\
+                shrunk in {} step(s), to an input which {}:\n\ninput: {}\n\n\
+                add it to the block as a case:\n\n    {}_regression: {{ {} }},\n\n\
+                or as a line of a regressions file:\n\n    {}\n\n\
+                rerun with `{}={:#x}` to reproduce it first",
+                name,
+                iteration,
+                iterations,
+                failure,
+                strategy.args(&input),
+                seed,
+                steps,
+                shrunk_failure,
+                shrunk,
+                name,
+                shrunk,
+                shrunk,
+                SEED,
+                seed
+            );
+        }
+
+        seed = Rng::new(seed).next_u64();
+    }
+}
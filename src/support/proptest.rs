@@ -0,0 +1,38 @@
+//! Integration of `proptest` strategies with property cases, only emitted for cases using them,
+//! as it requires the crate to depend on `proptest`.
+//!
+//! Values are generated by `proptest`, from a generator seeded by the property case,
+//! so inputs remain reproducible from the seed reported for them.
+use super::property::{Rng, Strategy};
+use ::proptest::{
+    strategy::{Strategy as ProptestStrategy, ValueTree},
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+
+/// A strategy producing values from a `proptest` strategy, produced by `proptest`.
+pub struct Proptest<S>(S);
+
+impl<S: ProptestStrategy> Strategy<S::Value> for Proptest<S> {
+    fn generate(&self, rng: &mut Rng) -> S::Value {
+        let mut seed = [0; 32];
+
+        for chunk in seed.chunks_mut(8) {
+            chunk.copy_from_slice(&rng.next_u64().to_le_bytes());
+        }
+
+        let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &seed);
+        let mut runner = TestRunner::new_with_rng(Config::default(), rng);
+
+        self.0
+            .new_tree(&mut runner)
+            .unwrap_or_else(|reason| {
+                panic!("`proptest` strategy couldn't produce a value: {}", reason)
+            })
+            .current()
+    }
+}
+
+/// Produces values from the `proptest` strategy `strategy`.
+pub fn proptest<S: ProptestStrategy>(strategy: S) -> Proptest<S> {
+    Proptest(strategy)
+}
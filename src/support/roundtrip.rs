@@ -0,0 +1,90 @@
+//! Comparison of values with the result of encoding and decoding them.
+//!
+//! Decoding functions may return the decoded value itself, or a `Result` or `Option` of it,
+//! which is distinguished by the type of the value they return, using autoref-based dispatch:
+//! `Decoding` implements the more specific traits directly, and a reference to it the fallback,
+//! so method resolution prefers the specific traits where they apply.
+use std::fmt::Debug;
+
+/// The value returned by a decoding function.
+pub struct Decoding<D>(pub D);
+
+/// Decoding functions returning a `Result`.
+pub trait DecodeResult {
+    type Value;
+
+    fn decoded(&self) -> Result<&Self::Value, String>;
+}
+
+impl<T, E: Debug> DecodeResult for Decoding<Result<T, E>> {
+    type Value = T;
+
+    fn decoded(&self) -> Result<&T, String> {
+        self.0.as_ref().map_err(|err| format!("{:?}", err))
+    }
+}
+
+/// Decoding functions returning an `Option`.
+pub trait DecodeOption {
+    type Value;
+
+    fn decoded(&self) -> Result<&Self::Value, String>;
+}
+
+impl<T> DecodeOption for Decoding<Option<T>> {
+    type Value = T;
+
+    fn decoded(&self) -> Result<&T, String> {
+        self.0.as_ref().ok_or_else(|| String::from("None"))
+    }
+}
+
+/// Decoding functions returning the decoded value itself.
+pub trait DecodeValue {
+    type Value;
+
+    fn decoded(&self) -> Result<&Self::Value, String>;
+}
+
+impl<T> DecodeValue for &Decoding<T> {
+    type Value = T;
+
+    fn decoded(&self) -> Result<&T, String> {
+        Ok(&self.0)
+    }
+}
+
+/// Produces the value decoded for the case `name`, panicking if it couldn't be decoded.
+pub fn decoded<'a, T>(name: &str, decoded: Result<&'a T, String>) -> &'a T {
+    decoded.unwrap_or_else(|err| panic!("case `{}` couldn't be decoded: {}", name, err))
+}
+
+/// Compares the `value` of the case `name`, with the value decoded from its encoded value.
+pub fn check<T, E>(name: &str, value: &T, encoded: &E, decoded: &T)
+where
+    T: PartialEq + Debug,
+    E: Debug + ?Sized,
+{
+    if decoded != value {
+        panic!(
+            "case `{}` doesn't survive a round trip:\n\nvalue: {:?}\nencoded: {:?}\ndecoded: {:?}\n",
+            name, value, encoded, decoded
+        );
+    }
+}
+
+/// Compares the `encoded` value of the canonical case `name`,
+/// with the value produced by encoding the value `decoded` from it.
+pub fn check_canonical<B, T, E>(name: &str, encoded: &B, decoded: &T, reencoded: &E)
+where
+    B: Debug + ?Sized,
+    T: Debug + ?Sized,
+    E: PartialEq<B> + Debug + ?Sized,
+{
+    if reencoded != encoded {
+        panic!(
+            "case `{}` isn't canonical:\n\nencoded: {:?}\ndecoded: {:?}\nreencoded: {:?}\n",
+            name, encoded, decoded, reencoded
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Comparison of the value returned by a test with a snapshot of it, stored in `tests/snapshots`.
+//!
+//! Snapshots which don't exist or don't match are written alongside the snapshot as pending
+//! (`.snap.new`) files, which are accepted by renaming them, or by rerunning the tests with blessing.
+use super::{bless, diff};
+use std::{fs, io::ErrorKind, path::PathBuf};
+
+/// Produces the path of the snapshot of the case `name`, within the module at `module_path`.
+pub fn path(module_path: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!(
+            "{}__{}.snap",
+            module_path.replace("::", "__"),
+            name
+        ))
+}
+
+/// Compares `actual` with the snapshot of the case `name`, within the module at `module_path`,
+/// panicking with a line diff, and writing a pending snapshot, if they differ.
+///
+/// When blessing, the snapshot is instead rewritten with `actual`.
+pub fn check(module_path: &str, name: &str, actual: String) {
+    let path = path(module_path, name);
+    let pending = path.with_extension("snap.new");
+    let actual = actual + "\n";
+
+    if bless::blessing() {
+        bless::write(&path, &actual);
+        let _ = fs::remove_file(&pending);
+        return;
+    }
+
+    // Checkouts may convert line endings, which shouldn't be reported as a difference.
+    let (message, diff) = match fs::read_to_string(&path) {
+        Ok(expected) => match diff::lines(&expected.replace("\r\n", "\n"), &actual) {
+            Some(diff) => ("doesn't match", diff),
+            None => {
+                let _ = fs::remove_file(&pending);
+                return;
+            }
+        },
+        Err(err) if err.kind() == ErrorKind::NotFound => (
+            "doesn't exist yet",
+            actual.lines().map(|line| format!("+ {}\n", line)).collect(),
+        ),
+        Err(err) => panic!("couldn't read `{}`: {}", path.display(), err),
+    };
+
+    bless::write(&pending, &actual);
+
+    panic!(
+        "snapshot `{}` {}:\n\n{}\nthe new snapshot was written to `{}`\n\
+        accept it by removing its `.new` extension, or by rerunning the tests with `{}=1`",
+        path.display(),
+        message,
+        diff,
+        pending.display(),
+        bless::BLESS
+    );
+}
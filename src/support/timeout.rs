@@ -0,0 +1,39 @@
+//! Running a case on a worker thread, failing it if it doesn't finish within its timeout.
+//!
+//! Threads can't be stopped from outside, so a worker which times out is left running,
+//! until the test binary exits, whilst the case itself fails immediately.
+use std::{
+    panic,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// Runs `run` on a worker thread, producing its output, and panicking if it isn't produced
+/// within `timeout`. Panics of the worker are resumed, so `should_panic` cases behave as usual.
+pub fn run<T, F>(name: &'static str, timeout: Duration, run: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let worker = thread::Builder::new()
+        .name(String::from(name))
+        .spawn(move || {
+            // The receiver is dropped once the case has timed out, so the output is discarded.
+            let _ = sender.send(run());
+        })
+        .expect("failed to spawn the worker thread of the case");
+
+    match receiver.recv_timeout(timeout) {
+        Ok(output) => output,
+        // The sender is only dropped without sending the output where the worker panicked.
+        Err(RecvTimeoutError::Disconnected) => match worker.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the worker finished without producing an output"),
+        },
+        Err(RecvTimeoutError::Timeout) => {
+            panic!("case `{}` timed out after {:?}", name, timeout)
+        }
+    }
+}
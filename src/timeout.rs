@@ -0,0 +1,118 @@
+//! Timeouts of cases, specified by `#[timeout(secs = 2)]`, failing cases which don't finish in time.
+//!
+//! Cases with a timeout are run on a worker thread, so the test itself can fail once the timeout
+//! has elapsed, rather than waiting on a case which may never finish.
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{punctuated::Punctuated, Attribute, Error, Lit, MetaNameValue, Result, Token, Type};
+
+/// The timeout of a case, in either seconds (`secs`) or milliseconds (`ms`).
+#[derive(Clone, Copy)]
+pub(crate) struct Timeout {
+    millis: u64,
+}
+
+impl Timeout {
+    /// Parses the timeout of a `timeout` attribute.
+    pub(crate) fn parse(attr: &Attribute) -> Result<Self> {
+        let error = || {
+            Error::new_spanned(
+                attr,
+                "expected a timeout in `secs` or `ms`, e.g. `#[timeout(secs = 2)]`",
+            )
+        };
+        let mut values = attr
+            .parse_args_with(Punctuated::<MetaNameValue, Token![,]>::parse_terminated)?
+            .into_iter();
+        let MetaNameValue { path, lit, .. } = values.next().ok_or_else(error)?;
+
+        if let Some(MetaNameValue { path, .. }) = values.next() {
+            return Err(Error::new_spanned(path, "timeout specified more than once"));
+        }
+
+        let value = match &lit {
+            Lit::Int(int) => int.base10_parse::<u64>()?,
+            lit => return Err(Error::new_spanned(lit, "expected an integer")),
+        };
+        let millis = if path.is_ident("secs") {
+            value.checked_mul(1_000)
+        } else if path.is_ident("ms") {
+            Some(value)
+        } else {
+            return Err(error());
+        };
+
+        Ok(Self {
+            millis: millis.ok_or_else(|| Error::new_spanned(&lit, "timeout is too large"))?,
+        })
+    }
+
+    /// Checks none of `attrs` is a `should_panic` attribute without an expected message,
+    /// as the panic of a case timing out would otherwise satisfy it, passing a case which hangs.
+    pub(crate) fn check_should_panic<'a>(
+        attrs: impl IntoIterator<Item = &'a Attribute>,
+    ) -> Result<()> {
+        match attrs
+            .into_iter()
+            .find(|attr| attr.path.is_ident("should_panic") && attr.tokens.is_empty())
+        {
+            Some(attr) => Err(Error::new_spanned(
+                attr,
+                "cases with a timeout must specify the message they panic with, \
+                e.g. `#[should_panic(expected = \"overflow\")]`, as timing out also panics",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Produces the body of the test of the case `name`, running `body` on a worker thread.
+    ///
+    /// Where the test returns a value of `return_type`, it's reported by the worker,
+    /// as the values returned by tests aren't required to be `Send` (e.g. `Box<dyn Error>`),
+    /// so the test instead returns the `ExitCode` reported, with any error already printed.
+    pub(crate) fn run(
+        self,
+        name: &str,
+        return_type: Option<&Type>,
+        body: TokenStream2,
+    ) -> TokenStream2 {
+        let Self { millis } = self;
+        let body = match return_type {
+            Some(return_type) => quote! {{
+                let __test_gen_output: #return_type = { #body };
+                ::std::process::Termination::report(__test_gen_output)
+            }},
+            None => quote!({ #body }),
+        };
+
+        quote! {
+            __test_gen::timeout::run(
+                #name,
+                ::std::time::Duration::from_millis(#millis),
+                move || #body,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn should_panic_expected() {
+        let check = |attrs: Vec<Attribute>| Timeout::check_should_panic(&attrs).is_ok();
+
+        assert!(check(vec![parse_quote!(#[ignore])]));
+        assert!(check(vec![
+            parse_quote!(#[should_panic(expected = "overflow")])
+        ]));
+        assert!(check(vec![parse_quote!(#[should_panic = "overflow"])]));
+        assert!(!check(vec![parse_quote!(#[should_panic])]));
+        assert!(!check(vec![
+            parse_quote!(#[ignore]),
+            parse_quote!(#[should_panic])
+        ]));
+    }
+}
@@ -0,0 +1,246 @@
+//! Type cases, lists of types named like test cases, for blocks which are instantiated per type,
+//! rather than calling a helper function per case (e.g. suites, and trait-bound assertions).
+//!
+//! Cases are named after the type in snake case (e.g. `MemStore` is named `mem_store`),
+//! unless renamed with `as`, which is required for types which aren't named by a path.
+use crate::{kw, variants};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    braced, bracketed,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token::{Brace, Bracket},
+    Attribute, Error, Ident, Result, Token, Type, TypeParamBound,
+};
+
+/// A type representing a type case, and optionally, the name it's renamed to.
+pub(crate) struct TypeCase {
+    pub(crate) attrs: Vec<Attribute>,
+    pub(crate) ty: Type,
+    rename: Option<(Token![as], Ident)>,
+}
+
+impl TypeCase {
+    /// Produces the `cfg` attributes of the case, as strings, for comparison with other cases.
+    fn cfgs(&self) -> Vec<String> {
+        self.attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("cfg"))
+            .map(|attr| attr.to_token_stream().to_string())
+            .collect()
+    }
+
+    /// Produces the name of the case, converting the name of the type into snake case,
+    /// unless renamed.
+    pub(crate) fn name(&self) -> Result<Ident> {
+        if let Some((_, name)) = &self.rename {
+            return Ok(name.clone());
+        }
+
+        match &self.ty {
+            Type::Path(ty) if ty.qself.is_none() => ty
+                .path
+                .segments
+                .last()
+                .ok_or_else(|| Error::new_spanned(ty, "expected type"))
+                .and_then(|segment| variants::case_name(&segment.ident))
+                .map_err(|_| {
+                    Error::new_spanned(ty, "expected a name for the type, e.g. `T as name`")
+                }),
+            ty => Err(Error::new_spanned(
+                ty,
+                "expected a name for the type, e.g. `(u8, u8) as pair`",
+            )),
+        }
+    }
+}
+
+impl Parse for TypeCase {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let ty = input.parse()?;
+        let rename = input.call(parse_rename)?;
+
+        Ok(Self { attrs, ty, rename })
+    }
+}
+
+impl ToTokens for TypeCase {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.ty.to_tokens(tokens);
+
+        if let Some((as_token, name)) = &self.rename {
+            as_token.to_tokens(tokens);
+            name.to_tokens(tokens);
+        }
+    }
+}
+
+/// Parses the name of a case, if renamed with `as`.
+pub(crate) fn parse_rename(input: ParseStream) -> Result<Option<(Token![as], Ident)>> {
+    input
+        .peek(Token![as])
+        .then(|| Ok((input.parse()?, input.parse()?)))
+        .transpose()
+}
+
+/// A type representing the brackets surrounding a list of type cases.
+pub(crate) struct TypeCases {
+    brackets: Bracket, // Preserved for span
+    cases: Punctuated<TypeCase, Token![,]>,
+}
+
+impl TypeCases {
+    /// Produces the name of every case, alongside the case itself.
+    ///
+    /// Names are only derived from the last segment of each type, so types differing
+    /// only by their path or generics (e.g. `Vec<u8>` and `Vec<u16>`) must be renamed,
+    /// unless their `cfg` attributes differ, as they may never be compiled together
+    /// (e.g. `#[cfg(unix)]` and `#[cfg(windows)]`). Otherwise, the compiler reports the duplicate.
+    pub(crate) fn named(self) -> Result<Vec<(Ident, TypeCase)>> {
+        let mut named: Vec<(Ident, TypeCase)> = Vec::with_capacity(self.cases.len());
+
+        for case in self.cases {
+            let name = case.name()?;
+            let cfgs = case.cfgs();
+
+            if named
+                .iter()
+                .any(|(existing, existing_case)| *existing == name && existing_case.cfgs() == cfgs)
+            {
+                return Err(Error::new_spanned(
+                    &case.ty,
+                    format!("duplicate case name `{}`, rename it with `as`", name),
+                ));
+            }
+
+            named.push((name, case));
+        }
+
+        Ok(named)
+    }
+}
+
+impl Parse for TypeCases {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let cases;
+        let brackets = bracketed!(cases in input);
+        let cases = cases.parse_terminated(TypeCase::parse)?;
+
+        if cases.is_empty() {
+            return Err(Error::new(brackets.span, "expected types"));
+        }
+
+        Ok(Self { brackets, cases })
+    }
+}
+
+impl ToTokens for TypeCases {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.brackets
+            .surround(tokens, |tokens| self.cases.to_tokens(tokens));
+    }
+}
+
+/// A type representing a block of `test_gen`, asserting a list of types implement some bounds.
+///
+/// This includes:
+///
+/// * The attributes to apply to every assertion
+/// * The bounds each type must satisfy
+/// * The types to assert the bounds of
+pub(crate) struct AssertImpl {
+    attrs: Vec<Attribute>,
+    assert_impl: kw::assert_impl,
+    braces: Brace, // Preserved for span
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    for_token: Token![for],
+    types: TypeCases,
+    semi: Option<Token![;]>,
+}
+
+impl AssertImpl {
+    /// Identifies whether the stream begins with an assertion, following any attributes.
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+
+        fork.call(Attribute::parse_outer).is_ok() && fork.peek(kw::assert_impl)
+    }
+
+    /// Produces an assertion for every type, checked at compile time.
+    ///
+    /// Each type is passed to a function named after its case, bounded by the bounds,
+    /// so unsatisfied bounds are reported as being required by the case.
+    /// The function is never called, so the assertions are zero-cost.
+    pub(crate) fn restructure(self) -> Result<TokenStream2> {
+        let Self {
+            attrs,
+            bounds,
+            types,
+            ..
+        } = self;
+
+        Ok(types
+            .named()?
+            .into_iter()
+            .map(|(name, case)| {
+                let TypeCase {
+                    attrs: case_attrs,
+                    ty,
+                    ..
+                } = case;
+                let check = quote_spanned!(name.span()=> #name::<#ty>());
+
+                quote! {
+                    #(#attrs)*
+                    #(#case_attrs)*
+                    const _: () = {
+                        #[allow(non_snake_case, dead_code)]
+                        fn #name<T: ?::core::marker::Sized + #bounds>() {}
+
+                        #[allow(dead_code)]
+                        fn assert_impl() {
+                            #check;
+                        }
+                    };
+                }
+            })
+            .collect())
+    }
+}
+
+impl Parse for AssertImpl {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let assert_impl = input.parse()?;
+
+        let bounds;
+        let braces = braced!(bounds in input);
+        let bounds = Punctuated::parse_separated_nonempty(&bounds)
+            .map_err(|err| Error::new(err.span(), "expected trait bounds, e.g. `Send + Sync`"))?;
+
+        Ok(Self {
+            attrs,
+            assert_impl,
+            braces,
+            bounds,
+            for_token: input.parse()?,
+            types: input.parse()?,
+            semi: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for AssertImpl {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        self.attrs.iter().for_each(|attr| attr.to_tokens(tokens));
+        self.assert_impl.to_tokens(tokens);
+        self.braces
+            .surround(tokens, |tokens| self.bounds.to_tokens(tokens));
+        self.for_token.to_tokens(tokens);
+        self.types.to_tokens(tokens);
+        self.semi.to_tokens(tokens);
+    }
+}
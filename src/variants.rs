@@ -0,0 +1,133 @@
+//! Enumeration of the variants of an enum as test cases, by `#[derive(TestGenVariants)]`.
+//!
+//! The derive produces a `macro_rules!` macro listing a case for every variant,
+//! which blocks splice into their own cases, with `for each variant of Enum`.
+use crate::data;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{ext::IdentExt, Data, DeriveInput, Error, Fields, Ident, Result};
+
+/// Produces the name of the macro listing the variants of the enum named `ident`.
+///
+/// The macro is only referred to by `test_gen` itself, so its name is derived from the enum,
+/// allowing blocks to refer to it by the name of the enum.
+pub(crate) fn macro_name(ident: &Ident) -> Ident {
+    format_ident!("__test_gen_variants_{}", ident)
+}
+
+/// Produces the name of the case for `variant`, converting its name into snake case.
+pub(crate) fn case_name(variant: &Ident) -> Result<Ident> {
+    let span = variant.span();
+    let variant = variant.unraw().to_string();
+    let mut name = String::with_capacity(variant.len() + 4);
+    let mut chars = variant.chars().peekable();
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        // Words begin at uppercase letters following lowercase letters or digits,
+        // or preceding lowercase letters after an acronym (e.g. `HTTPError`).
+        let after_word = previous
+            .filter(|previous| previous.is_lowercase() || previous.is_ascii_digit())
+            .is_some();
+        let after_acronym = previous
+            .filter(|previous| previous.is_uppercase())
+            .is_some()
+            && chars.peek().filter(|next| next.is_lowercase()).is_some();
+
+        if c.is_uppercase() && (after_word || after_acronym) {
+            name.push('_');
+        }
+
+        name.push(c);
+        previous = Some(c);
+    }
+
+    data::case_name(&name, span)
+        .ok_or_else(|| Error::new(span, format!("can't name a case after `{}`", variant)))
+}
+
+/// Produces the macro listing a case for every variant of the enum `input`.
+///
+/// The macro splices its cases into an invocation of `test_gen`, constructing each variant
+/// through the path the block specified the enum by. Fields of variants are constructed
+/// by their `Default` implementation, whilst `cfg` attributes of variants are applied to their case.
+pub(crate) fn derive(input: DeriveInput) -> Result<TokenStream2> {
+    let variants = match input.data {
+        Data::Enum(data) => data.variants,
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "`TestGenVariants` can only be derived for enums",
+            ))
+        }
+    };
+
+    let name = macro_name(&input.ident);
+    let cases = variants
+        .iter()
+        .map(|variant| {
+            let fn_name = case_name(&variant.ident)?;
+            let ident = &variant.ident;
+            let cfgs = variant
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"));
+            let fields = match &variant.fields {
+                Fields::Unit => quote!(),
+                Fields::Unnamed(fields) => {
+                    let fields = fields
+                        .unnamed
+                        .iter()
+                        .map(|_| quote!(::core::default::Default::default()));
+                    quote!((#(#fields),*))
+                }
+                Fields::Named(fields) => {
+                    let fields = fields.named.iter().map(|field| {
+                        let ident = &field.ident;
+                        quote!(#ident: ::core::default::Default::default())
+                    });
+                    quote!({ #(#fields),* })
+                }
+            };
+
+            Ok(quote! {
+                #fn_name: {
+                    #(#cfgs)*
+                    ($($path)*::#ident #fields)
+                },
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            (@test_gen_splice [$($test_gen:tt)*] [$($path:tt)*] { $($head:tt)* } { $($cases:tt)* }) => {
+                $($test_gen)*! { $($head)* { $($cases)* #(#cases)* } }
+            };
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn case_names() {
+        let name = |variant: &str| {
+            case_name(&format_ident!("{}", variant))
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(name("Red"), "red");
+        assert_eq!(name("BlackBerry"), "black_berry");
+        assert_eq!(name("HTTPError"), "http_error");
+        assert_eq!(name("Utf8"), "utf8");
+        assert_eq!(name("V2Beta"), "v2_beta");
+        assert_eq!(name("Type"), "r#type");
+        assert_eq!(name("ÉtéChaud"), "été_chaud");
+        assert_eq!(name("日本"), "日本");
+    }
+}
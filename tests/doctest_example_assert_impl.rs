@@ -0,0 +1,23 @@
+use std::sync::{Arc, Mutex};
+use test_gen::test_gen;
+
+pub struct Config {
+    pub name: String,
+}
+
+pub struct Cache<K, V> {
+    pub entries: Arc<Mutex<Vec<(K, V)>>>,
+}
+
+// Each type is checked whilst the crate is compiled, so a type which no longer
+// satisfies the bounds (e.g. after gaining an `Rc` field) fails the build,
+// with the error naming its case (e.g. `required by a bound in `cache``)
+test_gen! {
+    assert_impl { Send + Sync + 'static } for [
+        Config,
+        Cache<String, u32>,
+        (Config, u8) as pair,
+        #[cfg(unix)]
+        std::os::unix::net::UnixStream,
+    ]
+}
@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main};
+use test_gen::test_gen;
+
+fn validate(input: &str) {
+    assert!(
+        input.bytes().all(|byte| byte.is_ascii_digit()),
+        "not a number"
+    );
+}
+
+// Registers a benchmark for every case, named after the case, in a group named `validate`,
+// by a function named `validate_benches`, whilst still producing a test for every case
+test_gen! {
+    #[generate(tests, benches = criterion)]
+    fn validate => {
+        short: {
+            ("1")
+        },
+        long: {
+            ("123456")
+        },
+        // Cases which should panic are only tested, rather than benchmarked
+        invalid: {
+            #[should_panic]
+            ("one")
+        },
+    }
+}
+
+criterion_group!(benches, validate_benches);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use test_gen::test_gen;
+
+fn apply_offset(len: u16, offset: i32) -> u16 {
+    (i64::from(len) + i64::from(offset)).clamp(0, u16::MAX.into()) as u16
+}
+
+fn assert_offset_within(len: u16, offset: i32, low: u16, high: u16) {
+    let offset = apply_offset(len, offset);
+    assert!((low..=high).contains(&offset));
+}
+
+test_gen! {
+    fn assert_offset_within => {
+        // Produces a case for every boundary of `u16`
+        // (`len_min`, `len_min_plus_1`, `len_max_minus_1` and `len_max`),
+        // with the remaining arguments being passed to each case as they are
+        len: {
+            (test_gen::boundaries(u16), 0, 0, u16::MAX)
+        },
+        // Produces a case for every combination of the boundaries of `u16`, and of `i32`,
+        // including `1000`, and the values either side of it (e.g. `offset_max_around_plus_1`)
+        offset: {
+            (test_gen::boundaries(u16), test_gen::boundaries(i32, around = 1000), 0, u16::MAX)
+        },
+    }
+}
@@ -0,0 +1,33 @@
+use test_gen::test_gen;
+
+fn assert_sum_of_squares(n: u64, expected: u64) {
+    assert_eq!((1..=n).map(|i| i * i).sum::<u64>(), expected);
+}
+
+// Each case calls the helper function 100 times, failing if the median time exceeds 50ms
+test_gen! {
+    #[budget(ms = 50)]
+    fn assert_sum_of_squares => {
+        small: { (10, 385) },
+        large: {
+            // Overrides the budget of the block
+            #[budget(ms = 500, iterations = 10)]
+            (100_000, 333_338_333_350_000)
+        },
+    }
+}
+
+fn assert_sorted(mut values: Vec<u32>) {
+    values.sort_unstable();
+    assert!(values.windows(2).all(|pair| pair[0] <= pair[1]));
+}
+
+// Budgets can also be specified by cases alone, in microseconds
+test_gen! {
+    fn assert_sorted => {
+        reversed: {
+            #[budget(us = 500, iterations = 1000)]
+            ((0..100).rev().collect())
+        },
+    }
+}
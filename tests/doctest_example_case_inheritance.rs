@@ -0,0 +1,31 @@
+use test_gen::test_gen;
+
+fn assert_sum(values: Vec<u64>, start: u64, expected: u64) {
+    assert_eq!(values.into_iter().fold(start, |a, b| a + b), expected);
+}
+
+test_gen! {
+    fn assert_sum => {
+        base: {
+            (vec![1, 2, 3], 0, 6)
+        },
+        // Arguments are replaced by their index,
+        // with the remaining arguments copied from the extended case
+        offset: extends base {
+            1 = 4, 2 = 10
+        },
+        large: extends base {
+            0 = vec![1; 1 << 20], 2 = 1 << 20
+        },
+        // Attributes are added to those of the extended case, replacing any of the same name
+        // which a case can only specify once (e.g. `should_panic` and `ignore`, but not `cfg`),
+        // and cases can extend cases which themselves extend another
+        wrong_offset: extends offset {
+            #[should_panic]
+            2 = 6
+        },
+        wrong_offset_ignored: extends wrong_offset {
+            #[ignore]
+        },
+    }
+}
@@ -0,0 +1,40 @@
+use test_gen::{test_gen, test_gen_cases};
+
+fn assert_char_count(input: &str, expected: usize) {
+    assert_eq!(input.chars().count(), expected);
+}
+
+fn assert_round_trip(input: &str, _: usize) {
+    assert_eq!(String::from_utf8(input.as_bytes().to_vec()).unwrap(), input);
+}
+
+// Defines the cases once...
+test_gen_cases! {
+    name = utf8_edge_cases;
+    empty: { ("", 0) },
+    combining: { ("e\u{301}", 2) },
+    emoji: { ("\u{1F980}", 1) },
+}
+
+// ...which can then be used by any number of blocks,
+// alongside any cases of their own
+test_gen! {
+    fn assert_char_count => {
+        use cases utf8_edge_cases;
+
+        ascii: { ("abc", 3) },
+    }
+}
+
+// Case sets follow the same scoping rules as `macro_rules!` macros,
+// so can be used by blocks in nested modules
+mod round_trip {
+    use super::*;
+    use test_gen::test_gen;
+
+    test_gen! {
+        fn assert_round_trip => {
+            use cases utf8_edge_cases;
+        }
+    }
+}
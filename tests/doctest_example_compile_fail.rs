@@ -0,0 +1,38 @@
+use test_gen::test_gen;
+
+pub struct Open;
+pub struct Closed;
+
+pub struct Connection<State>(State);
+
+impl Connection<Closed> {
+    pub fn connect() -> Self {
+        Self(Closed)
+    }
+
+    pub fn open(self) -> Connection<Open> {
+        Connection(Open)
+    }
+}
+
+pub fn send(_: &Connection<Open>, message: &str) {
+    assert!(!message.is_empty());
+}
+
+// Compile-fail cases sit alongside the other cases of the block,
+// but are compiled as doctests by `cargo test --doc`,
+// so must be in the library itself, and refer to public items through `crate`
+// (e.g. `closed` is run as a doctest of the library `compile_fail/src/lib.rs` includes this in)
+test_gen! {
+    fn crate::send => {
+        open: { (&crate::Connection::connect().open(), "hello") },
+        closed: {
+            #[compile_fail(E0308)]
+            (&crate::Connection::connect(), "hello")
+        },
+        empty: {
+            #[should_panic]
+            (&crate::Connection::connect().open(), "")
+        },
+    }
+}
@@ -0,0 +1,49 @@
+use test_gen::test_gen;
+
+const fn checked_percent(part: u32, whole: u32) -> Option<u32> {
+    match whole {
+        0 => None,
+        whole => Some(part * 100 / whole),
+    }
+}
+
+const fn is_power_of_two(value: u64) -> bool {
+    value != 0 && value & (value - 1) == 0
+}
+
+// Each case is an assertion, evaluated whilst the crate is compiled,
+// so failing cases fail the build, naming the case
+test_gen! {
+    const fn is_power_of_two => {
+        one: { (1) },
+        large: { (1 << 40) },
+    }
+}
+
+// Values are compared with `==`, so are limited to types which can be compared in a constant
+// (e.g. integers, `bool` and `char`), unless the helper function compares them itself
+const fn percent_is(part: u32, whole: u32, expected: u32) -> bool {
+    match checked_percent(part, whole) {
+        Some(percent) => percent == expected,
+        None => false,
+    }
+}
+
+test_gen! {
+    const fn percent_is => {
+        half: { (1, 2, 50) },
+        whole: { (3, 3, 100) },
+    }
+}
+
+const fn mean(a: u32, b: u32) -> u32 {
+    a / 2 + b / 2 + (a % 2 + b % 2) / 2
+}
+
+test_gen! {
+    const fn mean => {
+        equal: { (4, 4) == 4 },
+        rounded_down: { (1, 2) == 1 },
+        max: { (u32::MAX, u32::MAX) == u32::MAX },
+    }
+}
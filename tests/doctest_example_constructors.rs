@@ -0,0 +1,83 @@
+use test_gen::test_gen;
+
+#[derive(Debug, PartialEq)]
+struct Percentage(u8);
+
+impl Percentage {
+    fn new(value: u8) -> Self {
+        assert!(value <= 100, "percentages can't exceed 100");
+        Self(value)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self.0 {
+            0..=100 => Ok(()),
+            value => Err(format!("{} exceeds 100", value)),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Shape {
+    Circle(f32),
+    Rect(f32, f32),
+}
+
+impl Shape {
+    fn is_valid(&self) -> bool {
+        match *self {
+            Self::Circle(radius) => radius >= 0.0,
+            Self::Rect(width, height) => width >= 0.0 && height >= 0.0,
+        }
+    }
+}
+
+// Every value constructed by the cases is checked with `Percentage::validate`...
+test_gen! {
+    struct Percentage check = Percentage::validate => {
+        zero: { (0) },
+        full: { (100) },
+        exceeded: {
+            #[should_panic]
+            (101)
+        },
+    }
+}
+
+// ...including those constructed through a constructor function
+test_gen! {
+    struct Percentage::new check = Percentage::validate => {
+        half: {
+            #[expect_debug("Percentage(50)")]
+            (50)
+        },
+        exceeded_new: {
+            #[should_panic]
+            (101)
+        },
+    }
+}
+
+// Variants can similarly be constructed by `enum` blocks
+test_gen! {
+    enum Shape::Circle check = Shape::is_valid => {
+        unit_circle: { (1.0) },
+        negative_radius: {
+            #[should_panic]
+            (-1.0)
+        },
+    }
+}
+
+test_gen! {
+    enum Shape::Rect check = Shape::is_valid => {
+        square: {
+            #[expect_debug("Rect(1.0, 1.0)")]
+            (1.0, 1.0)
+        },
+        negative: {
+            #[should_panic]
+            (-1.0, 1.0)
+        },
+    }
+}
@@ -0,0 +1,44 @@
+use test_gen::test_gen;
+
+fn truncate(input: &str, max: usize) -> &str {
+    match input.char_indices().nth(max) {
+        Some((end, _)) => &input[..end],
+        None => input,
+    }
+}
+
+fn assert_truncates(input: &str, max: usize) {
+    let truncated = truncate(input, max);
+
+    assert!(truncated.chars().count() <= max);
+    assert!(input.starts_with(truncated));
+}
+
+fn assert_lossless_when_valid(input: &[u8]) {
+    let lossy = String::from_utf8_lossy(input);
+    assert_eq!(
+        std::str::from_utf8(input).is_ok(),
+        lossy.as_bytes() == input
+    );
+}
+
+// Produces a case for every entry of the corpus (e.g. `truncated_empty`,
+// `truncated_combining` and `truncated_emoji_zwj`), with the remaining arguments
+// being passed to each case as they are
+test_gen! {
+    fn assert_truncates => {
+        truncated: {
+            (test_gen::strings::edge, 1)
+        },
+    }
+}
+
+// Produces a case for every entry of the corpus
+// (e.g. `decoded_invalid_utf8`, `decoded_utf8_bom` and `decoded_ff_run`)
+test_gen! {
+    fn assert_lossless_when_valid => {
+        decoded: {
+            (test_gen::bytes::edge)
+        },
+    }
+}
@@ -0,0 +1,21 @@
+use test_gen::test_gen;
+
+fn assert_char_count(input: &str, expected: usize) {
+    assert_eq!(input.chars().count(), expected);
+}
+
+// Loads a case for every row of `tests/data/char_count.csv`,
+// relative to the crate's `Cargo.toml`:
+//
+// name,input: str,expected
+// empty,,0
+// ascii,abc,3
+// "Two Words","a, b",4
+// quoted,"say ""hi""",8
+test_gen! {
+    fn assert_char_count => {
+        cases from csv "tests/data/char_count.csv";
+
+        emoji: { ("\u{1F980}", 1) },
+    }
+}
@@ -0,0 +1,42 @@
+use test_gen::test_gen;
+
+mod fast {
+    pub fn count_words(input: &str) -> usize {
+        let mut count = 0;
+        let mut in_word = false;
+
+        for byte in input.bytes() {
+            let is_space = byte.is_ascii_whitespace();
+
+            if !is_space && !in_word {
+                count += 1;
+            }
+
+            in_word = !is_space;
+        }
+
+        count
+    }
+}
+
+mod reference {
+    pub fn count_words(input: &str) -> usize {
+        input.split_ascii_whitespace().count()
+    }
+}
+
+// Every case calls both implementations with the same arguments,
+// and fails if their outputs differ, reporting both outputs
+test_gen! {
+    fn fast::count_words vs reference::count_words => {
+        empty: {
+            ("")
+        },
+        single: {
+            ("crab")
+        },
+        surrounded: {
+            ("  ferris  the\tcrab\n")
+        },
+    }
+}
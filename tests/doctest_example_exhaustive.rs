@@ -0,0 +1,36 @@
+use test_gen::test_gen;
+
+enum Fruit {
+    Apple,
+    Pear,
+    Other(&'static str),
+}
+
+fn name_of(fruit: &Fruit) -> &str {
+    match fruit {
+        Fruit::Apple => "apple",
+        Fruit::Pear => "pear",
+        Fruit::Other(name) => name,
+    }
+}
+
+fn assert_named(fruit: Fruit, name: &str) {
+    assert_eq!(name_of(&fruit), name);
+}
+
+// Fails to compile, if a variant of `Fruit` isn't passed to `assert_named` by any case,
+// naming the missing variant, e.g. "non-exhaustive patterns: `&Fruit::Pear` not covered"
+test_gen! {
+    fn assert_named exhaustive over Fruit => {
+        apple: {
+            (Fruit::Apple, "apple")
+        },
+        pear: {
+            (Fruit::Pear, "pear")
+        },
+        // The fields of variants don't need to be covered
+        blackberry: {
+            (Fruit::Other("blackberry"), "blackberry")
+        },
+    }
+}
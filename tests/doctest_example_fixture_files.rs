@@ -0,0 +1,45 @@
+use test_gen::test_gen;
+
+// The expected line count is specified by the name of each fixture
+fn assert_line_count(name: &str, contents: &str) {
+    let expected = match name {
+        "empty" => 0,
+        "one" => 1,
+        "two" => 2,
+        _ => unreachable!(),
+    };
+
+    assert_eq!(contents.lines().count(), expected);
+}
+
+fn assert_fixture(path: &str) {
+    let name = std::path::Path::new(path)
+        .file_stem()
+        .unwrap()
+        .to_str()
+        .unwrap();
+
+    assert_line_count(name, &std::fs::read_to_string(path).unwrap());
+}
+
+// Produces a case for every file matching the pattern,
+// relative to the crate's `Cargo.toml`, passing the path of the file
+// (`nested_empty`, `one`, and `two`)
+test_gen! {
+    fn assert_fixture => {
+        cases from glob "tests/fixtures/lines/**/*.txt";
+    }
+}
+
+// Files can also be included, as a `&str` (`as str`),
+// or as bytes (`as bytes`)
+mod included {
+    use super::*;
+    use test_gen::test_gen;
+
+    test_gen! {
+        fn assert_line_count ("one") => {
+            cases from glob "tests/fixtures/lines/one.*" as str;
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use test_gen::test_gen;
+
+fn shout(input: &str) -> String {
+    input.to_uppercase()
+}
+
+// Produces a case for every input file matching the pattern (`crab` and `greeting`),
+// comparing the output of the helper function, with the file of the same name
+// and an `out` extension (e.g. `tests/golden/shout/crab.out`)
+//
+// Running the tests with `TEST_GEN_BLESS=1` rewrites the `.out` files
+// with the output of each case, rather than comparing them
+test_gen! {
+    fn shout => {
+        cases from golden "tests/golden/shout/*.in";
+    }
+}
+
+// Individual cases can also be compared with an expected output,
+// using the `golden` attribute
+mod attribute {
+    use super::*;
+    use test_gen::test_gen;
+
+    test_gen! {
+        fn shout => {
+            greeting: {
+                #[golden("tests/golden/shout/greeting.out")]
+                ("hello\nworld\n")
+            },
+        }
+    }
+}
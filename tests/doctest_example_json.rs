@@ -0,0 +1,41 @@
+use test_gen::test_gen;
+
+fn assert_char_count(input: &str, expected: usize) {
+    assert_eq!(input.chars().count(), expected);
+}
+
+// Loads a case for every entry of `tests/data/char_count.json`,
+// relative to the crate's `Cargo.toml`, with arrays passed as the arguments of the case:
+//
+// {
+//     "empty": ["", 0],
+//     "ascii": ["abc", 3],
+//     "Combining Mark": ["é", 2]
+// }
+test_gen! {
+    fn assert_char_count => {
+        cases from json "tests/data/char_count.json";
+    }
+}
+
+struct Config {
+    verbose: bool,
+    depth: u8,
+}
+
+fn assert_valid(config: Config) {
+    assert!(config.depth > 0);
+    assert_eq!(config.verbose, config.depth > 4);
+}
+
+// Tables are converted into struct literals of the type specified by `as`:
+//
+// {
+//     "shallow": { "verbose": false, "depth": 1 },
+//     "deep": { "verbose": true, "depth": 8 }
+// }
+test_gen! {
+    fn assert_valid => {
+        cases from json "tests/data/configs.json" as Config;
+    }
+}
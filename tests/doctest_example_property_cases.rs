@@ -0,0 +1,57 @@
+use test_gen::test_gen;
+
+fn midpoint(a: u32, b: u32) -> u32 {
+    a / 2 + b / 2 + (a % 2 + b % 2) / 2
+}
+
+fn assert_midpoint_between(a: u32, b: u32) {
+    let mid = midpoint(a, b);
+
+    assert!(a.min(b) <= mid && mid <= a.max(b));
+}
+
+// Helper functions can also report failures by returning `false`, or an `Err`
+fn is_symmetric(a: u32, b: u32) -> bool {
+    midpoint(a, b) == midpoint(b, a)
+}
+
+fn sorted(mut items: Vec<u8>) -> Result<(), String> {
+    items.sort_unstable();
+
+    match items.windows(2).all(|pair| pair[0] <= pair[1]) {
+        true => Ok(()),
+        false => Err(format!("{:?} isn't sorted", items)),
+    }
+}
+
+// The arguments of property cases are strategies, producing the inputs
+// the case is run on (256 by default), from a seed derived from the name of the case
+//
+// Failing inputs are reported alongside the seed they were produced from,
+// which can be used to reproduce them, by setting `TEST_GEN_SEED`
+test_gen! {
+    fn assert_midpoint_between => {
+        between: property {
+            (any::<u32>(), any::<u32>())
+        },
+        between_small: property(1000) {
+            (0..10, 0..=10)
+        },
+    }
+}
+
+test_gen! {
+    fn is_symmetric => {
+        symmetric: property {
+            (any::<u32>(), any::<u32>())
+        },
+    }
+}
+
+test_gen! {
+    fn sorted => {
+        sorts: property {
+            (vec_of(any::<u8>(), 0..64))
+        },
+    }
+}
@@ -0,0 +1,33 @@
+use test_gen::test_gen;
+
+fn insertion_sort(mut items: Vec<i32>) -> Vec<i32> {
+    for i in 1..items.len() {
+        let mut j = i;
+
+        while j > 0 && items[j - 1] > items[j] {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+
+    items
+}
+
+fn assert_sorts(items: Vec<i32>) {
+    let mut expected = items.clone();
+    expected.sort();
+
+    assert_eq!(insertion_sort(items), expected);
+}
+
+// Produces a case for every entry of the file, named after its line
+// (`insertion_sort_0002`, `insertion_sort_0003` and `insertion_sort_0005`),
+// alongside the cases of the block
+test_gen! {
+    fn assert_sorts => {
+        regressions "tests/regressions/insertion_sort.txt";
+        sorts: property {
+            (vec_of(-100..100, 0..=64))
+        },
+    }
+}
@@ -0,0 +1,35 @@
+use test_gen::test_gen;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16))
+        .collect()
+}
+
+// The value of every case is encoded, and decoded again,
+// and compared with the decoded value (`from_hex(&to_hex(&value)) == value`)
+//
+// Values are passed to the encoding function by reference, as is the encoded value
+// to the decoding function, which may also return a `Result` or `Option`
+//
+// Canonical cases instead specify an encoded value, which must be reproduced
+// by encoding the value it decodes to (`to_hex(&from_hex(&encoded[..])?) == encoded`)
+test_gen! {
+    roundtrip fn to_hex, fn from_hex => {
+        empty: {
+            (Vec::new())
+        },
+        bytes: {
+            (vec![0x00, 0x7f, 0xff])
+        },
+        lowercase: {
+            #[canonical]
+            ("c0ffee")
+        },
+    }
+}
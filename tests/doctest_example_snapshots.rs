@@ -0,0 +1,50 @@
+use test_gen::test_gen;
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Word {
+    text: String,
+    vowels: usize,
+}
+
+fn analyse(text: &str) -> Word {
+    Word {
+        text: text.to_owned(),
+        vowels: text.chars().filter(|c| "aeiou".contains(*c)).count(),
+    }
+}
+
+// The value returned by the helper function is formatted with `Debug`,
+// and compared with the snapshot named after the module and case
+// (e.g. `tests/snapshots/doctest_example_snapshots__crab.snap`)
+//
+// Snapshots which don't exist, or don't match, are written as pending files
+// (e.g. `doctest_example_snapshots__crab.snap.new`), and the case fails
+// until they're accepted, by renaming them, or rerunning the tests with `TEST_GEN_BLESS=1`
+test_gen! {
+    fn analyse -> snapshot => {
+        crab: {
+            ("crab")
+        },
+        ferris: {
+            ("ferris")
+        },
+    }
+}
+
+// Values can instead be formatted with `Display`,
+// which can also be specified on a case-by-case basis
+mod display {
+    use test_gen::test_gen;
+
+    test_gen! {
+        fn str::to_uppercase -> snapshot(Display) => {
+            shout: {
+                ("crab")
+            },
+            debug: {
+                ("crab") -> snapshot(Debug)
+            },
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use test_gen::test_gen;
+
+struct Case {
+    input: &'static str,
+    expected: usize,
+    trim: bool,
+}
+
+const BASE: Case = Case {
+    input: "",
+    expected: 0,
+    trim: false,
+};
+
+fn assert_len(case: Case) {
+    let input = if case.trim {
+        case.input.trim()
+    } else {
+        case.input
+    };
+
+    assert_eq!(input.len(), case.expected);
+}
+
+// Struct-literal cases are wrapped in the type specified by the `with` clause,
+// with any fields not specified by the case, taken from the base expression
+test_gen! {
+    fn assert_len with Case { ..BASE } => {
+        three: { input: "abc", expected: 3 },
+        trimmed: { input: "   ", trim: true },
+        untrimmed: {
+            #[should_panic]
+            input: " abc ", expected: 3
+        },
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::{BTreeMap, HashMap};
+use test_gen::{test_gen, test_gen_suite};
+
+trait Store: Default {
+    fn put(&mut self, key: &str, value: u32);
+    fn get(&self, key: &str) -> Option<u32>;
+}
+
+impl Store for HashMap<String, u32> {
+    fn put(&mut self, key: &str, value: u32) {
+        self.insert(key.to_owned(), value);
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        HashMap::get(self, key).copied()
+    }
+}
+
+impl Store for BTreeMap<String, u32> {
+    fn put(&mut self, key: &str, value: u32) {
+        self.insert(key.to_owned(), value);
+    }
+
+    fn get(&self, key: &str) -> Option<u32> {
+        BTreeMap::get(self, key).copied()
+    }
+}
+
+// The checks are written once, generic over the type...
+fn put_then_get<S: Store>() {
+    let mut store = S::default();
+    store.put("key", 1);
+    assert_eq!(store.get("key"), Some(1));
+}
+
+fn put_overwrites<S: Store>() {
+    let mut store = S::default();
+    store.put("key", 1);
+    store.put("key", 2);
+    assert_eq!(store.get("key"), Some(2));
+}
+
+fn get_missing<S: Store>() -> Result<(), String> {
+    match S::default().get("key") {
+        None => Ok(()),
+        Some(value) => Err(format!("found {}", value)),
+    }
+}
+
+// ...listed as a suite...
+test_gen_suite! {
+    name = store_conformance;
+
+    fn put_then_get,
+    fn put_overwrites as overwrite,
+    fn get_missing -> Result<(), String>,
+}
+
+// ...and instantiated for every type, producing the modules `hash_map` and `b_tree`,
+// each containing the tests `put_then_get`, `overwrite` and `get_missing`
+test_gen! {
+    instantiate suite store_conformance for [
+        HashMap<String, u32>,
+        BTreeMap<String, u32> as b_tree,
+    ]
+}
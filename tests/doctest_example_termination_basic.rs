@@ -6,9 +6,7 @@ mod case_by_case {
     struct Example;
 
     impl From<Example> for () {
-        fn from(_: Example) -> Self {
-            ()
-        }
+        fn from(_: Example) -> Self {}
     }
 
     test_gen! {
@@ -0,0 +1,40 @@
+use std::{error::Error, sync::mpsc, time::Duration};
+use test_gen::test_gen;
+
+fn assert_received(messages: Vec<&str>) {
+    let (sender, receiver) = mpsc::channel();
+
+    for message in &messages {
+        sender.send(message.to_string()).unwrap();
+    }
+
+    // Hangs if fewer messages were sent than are expected
+    for message in messages {
+        assert_eq!(receiver.recv().unwrap(), message);
+    }
+}
+
+// Each case fails if it doesn't finish within 2 seconds
+test_gen! {
+    #[timeout(secs = 2)]
+    fn assert_received => {
+        empty: { (vec![]) },
+        several: { (vec!["a", "b", "c"]) },
+    }
+}
+
+fn sleep_for(millis: &str) -> Result<(), Box<dyn Error>> {
+    let millis = millis.parse()?;
+    std::thread::sleep(Duration::from_millis(millis));
+    Ok(())
+}
+
+// Returned values are reported as usual, even though `Box<dyn Error>` isn't `Send`
+test_gen! {
+    fn sleep_for -> Result<(), Box<dyn Error>> => {
+        short: {
+            #[timeout(ms = 500)]
+            ("10")
+        },
+    }
+}
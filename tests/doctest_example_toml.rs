@@ -0,0 +1,25 @@
+use test_gen::test_gen;
+
+struct Case {
+    input: &'static str,
+    expected: usize,
+}
+
+fn assert_char_count(case: Case) {
+    assert_eq!(case.input.chars().count(), case.expected);
+}
+
+// Loads a case for every table of `tests/data/char_count.toml`,
+// relative to the crate's `Cargo.toml`, converted into a struct literal of `Case`:
+//
+// empty = { input = "", expected = 0 }
+// ascii = { input = "abc", expected = 3 }
+//
+// ["Combining Mark"]
+// input = "é"
+// expected = 2
+test_gen! {
+    fn assert_char_count => {
+        cases from toml "tests/data/char_count.toml" as Case;
+    }
+}
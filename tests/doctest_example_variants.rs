@@ -0,0 +1,36 @@
+use test_gen::{test_gen, TestGenVariants};
+
+#[derive(TestGenVariants)]
+enum Colour {
+    Red,
+    Green,
+    Blue,
+    // Fields are constructed with their `Default` implementation
+    Grey(u8),
+    Custom { red: u8, green: u8, blue: u8 },
+}
+
+fn to_hex(colour: Colour) -> String {
+    let (red, green, blue) = match colour {
+        Colour::Red => (0xff, 0, 0),
+        Colour::Green => (0, 0xff, 0),
+        Colour::Blue => (0, 0, 0xff),
+        Colour::Grey(shade) => (shade, shade, shade),
+        Colour::Custom { red, green, blue } => (red, green, blue),
+    };
+
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+fn assert_hex(colour: Colour) {
+    assert_eq!(to_hex(colour).len(), 7);
+}
+
+// Produces a case for every variant of `Colour`
+// (`red`, `green`, `blue`, `grey` and `custom`),
+// so adding a variant automatically adds a case for it
+test_gen! {
+    fn assert_hex => {
+        for each variant of Colour;
+    }
+}
@@ -66,9 +66,7 @@ mod unique_return {
     struct Test;
 
     impl From<Test> for () {
-        fn from(_: Test) -> Self {
-            ()
-        }
+        fn from(_: Test) -> Self {}
     }
 
     test_gen! {
@@ -173,6 +171,1296 @@ mod static_args {
     }
 }
 
-fn bool_panic(switch: bool) {
-    assert!(switch);
+mod struct_literal {
+    use super::*;
+
+    #[derive(Default)]
+    struct Case {
+        a: usize,
+        b: usize,
+        sum: usize,
+    }
+
+    fn assert_sum(offset: usize, case: Case) {
+        assert_eq!(offset + case.a + case.b, case.sum);
+    }
+
+    test_gen! {
+        fn assert_sum (0) with Case => {
+            one_all_fields: {
+                a: 1, b: 2, sum: 3
+            },
+            two_trailing_comma: {
+                a: 2, b: 2, sum: 4,
+            },
+        }
+    }
+
+    test_gen! {
+        fn assert_sum (1) with Case { ..Default::default() } => {
+            three_defaults: {
+                sum: 1
+            },
+            four_override: {
+                b: 2, sum: 3
+            },
+            five_wrong_sum: {
+                #[should_panic]
+                a: 1, sum: 1
+            },
+        }
+    }
+
+    struct Mapped {
+        value: u8,
+        map: fn(u8) -> u8,
+    }
+
+    fn mapped(case: Mapped) -> Result<(), String> {
+        match (case.map)(case.value) {
+            0 => Err(String::from("mapped to zero")),
+            _ => Ok(()),
+        }
+    }
+
+    // Arrows of closures and function types within fields don't end the fields of the case
+    test_gen! {
+        fn mapped -> Result<(), String> with Mapped => {
+            closure_return_type: {
+                value: 1, map: |x| -> u8 { x }
+            },
+            function_type: {
+                value: 1, map: (|x| x) as fn(u8) -> u8
+            },
+            case_return_type: {
+                value: 1, map: |x| -> u8 { x + 1 } -> Result<(), String>
+            },
+        }
+    }
+}
+
+mod inheritance {
+    use super::*;
+
+    #[derive(Default)]
+    struct Case {
+        a: usize,
+        b: usize,
+        sum: usize,
+        label: &'static str,
+    }
+
+    fn assert_sum(case: Case) {
+        assert_eq!(case.a + case.b, case.sum, "{}", case.label);
+    }
+
+    test_gen! {
+        fn assert_sum with Case { ..Default::default() } => {
+            one_base: {
+                a: 1, sum: 1
+            },
+            two_named_override: extends one_base {
+                #[should_panic]
+                sum = 3
+            },
+            three_appended_field: extends four_chained {
+                label = "appended"
+            },
+            four_chained: extends two_named_override {
+                a = 1 -> ()
+            },
+        }
+    }
+
+    test_gen! {
+        fn bool_panic => {
+            five_base: {
+                (true)
+            },
+            six_unchanged: extends five_base {},
+            seven_index_override: extends five_base {
+                #[should_panic]
+                0 = false
+            },
+            // Inherits `should_panic`, alongside its own attribute
+            eight_attrs_only: extends seven_index_override {
+                #[ignore]
+            },
+            nine_replaced_attr: extends seven_index_override {
+                #[should_panic(expected = "assertion failed")]
+            },
+        }
+    }
+
+    // Attributes which stack, such as `cfg`, are inherited alongside those of the case,
+    // so `eleven_both_cfgs` is only compiled where both apply, which is never
+    test_gen! {
+        fn bool_panic => {
+            ten_cfg_base: {
+                #[cfg(any())]
+                (false)
+            },
+            eleven_both_cfgs: extends ten_cfg_base {
+                #[cfg(all())]
+            },
+        }
+    }
+}
+
+mod case_sets {
+    use super::*;
+
+    test_gen_cases! {
+        name = bool_cases;
+        one_true: {
+            (true)
+        },
+        two_false: {
+            #[should_panic]
+            (false)
+        },
+    }
+
+    test_gen_cases! {
+        #[macro_export]
+        name = test_gen_exported_cases;
+        three_extends_set: extends one_true {
+            #[ignore]
+        },
+    }
+
+    test_gen! {
+        fn bool_panic => {
+            use cases bool_cases;
+            use cases test_gen_exported_cases;
+
+            four_own_case: {
+                (true)
+            }
+        }
+    }
+
+    mod nested {
+        use super::*;
+
+        test_gen! {
+            #[ignore]
+            fn bool_panic => {
+                use cases bool_cases;
+            }
+        }
+    }
+}
+
+mod boundaries {
+    use super::*;
+
+    fn assert_in_range<T: PartialOrd + std::fmt::Debug>(value: T, low: T, high: T) {
+        assert!(low <= value && value <= high, "{:?}", value);
+    }
+
+    test_gen! {
+        fn assert_in_range => {
+            // Values either side of `around`, which are out of range, are skipped
+            unsigned: {
+                (test_gen::boundaries(u8, around = 255), 0, u8::MAX)
+            },
+            signed: extends unsigned {
+                0 = test_gen::boundaries(i8, around = -128),
+                1 = i8::MIN,
+                2 = i8::MAX,
+            },
+            r#type: {
+                (test_gen::boundaries(u64), 0, u64::MAX)
+            },
+            empty: {
+                #[should_panic]
+                (test_gen::boundaries(i16), 1, 0)
+            },
+        }
+    }
+
+    test_gen! {
+        fn assert_in_range => {
+            // Sizes are limited to the range of `u32`, whatever the target
+            size: {
+                (test_gen::boundaries(usize, around = 4_294_967_295), 0, usize::MAX)
+            },
+        }
+    }
+
+    /// A function sharing the name of the generator, which is only expanded when qualified.
+    fn boundaries(low: u8) -> u8 {
+        low.saturating_add(1)
+    }
+
+    test_gen! {
+        fn assert_in_range => {
+            unqualified: {
+                (boundaries(0), 1, 1)
+            },
+        }
+    }
+
+    struct Case {
+        value: u8,
+        widened: u32,
+    }
+
+    fn assert_widens(case: Case) {
+        assert!(u32::from(case.value) <= case.widened || case.widened < 256);
+    }
+
+    test_gen! {
+        fn assert_widens with Case => {
+            fields: {
+                value: test_gen::boundaries(u8),
+                widened: test_gen::boundaries(u32, around = 256),
+            },
+        }
+    }
+}
+
+mod corpora {
+    use super::*;
+
+    fn assert_lossy_len<T: AsRef<[u8]>>(input: T) {
+        let input = input.as_ref();
+        assert!(String::from_utf8_lossy(input).len() >= input.len());
+    }
+
+    fn assert_prefix(input: &[u8], prefix: &[u8]) {
+        assert!(input.starts_with(prefix));
+    }
+
+    test_gen! {
+        fn assert_lossy_len => {
+            strings: {
+                (test_gen::strings::edge)
+            },
+            bytes: {
+                (test_gen::bytes::edge)
+            },
+        }
+    }
+
+    test_gen! {
+        fn assert_prefix => {
+            empty: {
+                (test_gen::bytes::edge, b"")
+            },
+            start_of_heading: extends empty {
+                #[should_panic]
+                1 = b"\x01"
+            },
+        }
+    }
+
+    /// A module sharing the name of a corpus, which is only expanded when qualified.
+    mod bytes {
+        #[allow(non_upper_case_globals)]
+        pub const edge: &[u8] = b"\x01edge";
+    }
+
+    test_gen! {
+        fn assert_prefix => {
+            unqualified: {
+                (bytes::edge, b"\x01")
+            },
+        }
+    }
+}
+
+mod exhaustive {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Direction<T> {
+        Up,
+        Down,
+        By(T),
+        Towards { x: T, y: T },
+    }
+
+    impl Direction<i8> {
+        fn new(offset: i8) -> Self {
+            match offset {
+                1 => Self::Up,
+                -1 => Self::Down,
+                _ => Self::By(offset),
+            }
+        }
+    }
+
+    fn assert_vertical(direction: Direction<i8>, vertical: bool) {
+        assert_eq!(
+            matches!(direction, Direction::Up | Direction::Down),
+            vertical
+        );
+    }
+
+    struct Case {
+        direction: Direction<i8>,
+        reversed: Direction<i8>,
+    }
+
+    fn assert_reversed(case: Case) {
+        let reversed = match case.direction {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::By(by) => Direction::By(-by),
+            Direction::Towards { x, y } => Direction::Towards { x: -x, y: -y },
+        };
+
+        assert_eq!(reversed, case.reversed);
+    }
+
+    test_gen! {
+        fn assert_vertical exhaustive over Direction<i8> => {
+            up: {
+                (Direction::Up, true)
+            },
+            down: extends up {
+                0 = self::Direction::Down
+            },
+            by: {
+                (Direction::By(1), false)
+            },
+            towards: {
+                (Direction::Towards { x: 1, y: 1 }, false)
+            },
+            // Calls of associated functions are ignored, rather than matched as variants
+            constructed: {
+                (Direction::new(1), true)
+            },
+        }
+    }
+
+    test_gen! {
+        fn assert_reversed with Case exhaustive over self::Direction<i8> => {
+            reversed_up: {
+                direction: self::Direction::Up,
+                reversed: self::Direction::Down,
+            },
+            reversed_by: {
+                direction: self::Direction::By(2),
+                reversed: self::Direction::By(-2),
+            },
+            reversed_towards: {
+                direction: self::Direction::Towards { x: 0, y: 1 },
+                reversed: self::Direction::Towards { x: 0, y: -1 },
+            },
+        }
+    }
+}
+
+mod variants {
+    use super::*;
+    use test_gen::TestGenVariants;
+
+    #[derive(TestGenVariants, Debug, PartialEq)]
+    pub enum Token<T> {
+        Type,
+        Number(T),
+        Pair(T, T),
+        Named {
+            value: T,
+        },
+        #[cfg(any())]
+        Disabled,
+    }
+
+    fn assert_defaults(default: u8, token: Token<u8>) {
+        match token {
+            Token::Type => {}
+            Token::Number(value) | Token::Named { value } => assert_eq!(value, default),
+            Token::Pair(first, second) => assert_eq!((first, second), (default, default)),
+        }
+    }
+
+    mod nested {
+        use super::*;
+
+        // Variants are constructed through the path specified by the block,
+        // alongside the cases of the block, and its exhaustiveness check
+        test_gen! {
+            fn assert_defaults (0) exhaustive over super::Token<u8> => {
+                for each variant of super::Token::<u8>;
+                own: {
+                    (Token::Number(0))
+                },
+                own_pair: {
+                    #[should_panic]
+                    (Token::Pair(0, 1))
+                },
+            }
+        }
+    }
+
+    // Non-ASCII characters are kept in the names of cases (e.g. `crème_brûlée`)
+    #[derive(TestGenVariants)]
+    pub enum Dessert {
+        CrèmeBrûlée,
+        Éclair,
+    }
+
+    fn assert_french(_: Dessert) {}
+
+    test_gen! {
+        fn assert_french => {
+            for each variant of Dessert;
+        }
+    }
+}
+
+mod constructors {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    struct Even(u32);
+
+    impl Even {
+        fn doubled(half: u16) -> Self {
+            Self(u32::from(half) * 2)
+        }
+
+        fn is_even(&self) -> bool {
+            self.0 & 1 == 0
+        }
+
+        fn assert_even(&self) {
+            assert_eq!(self.0 & 1, 0);
+        }
+    }
+
+    test_gen! {
+        struct Even check = Even::is_even => {
+            zero: {
+                (0)
+            },
+            odd: {
+                #[should_panic(expected = "case `odd` violated the invariant `Even::is_even`, which returned `false`")]
+                (1)
+            },
+            debug: {
+                #[expect_debug("Even(2)")]
+                (2)
+            },
+            debug_mismatch: {
+                #[should_panic(expected = "case `debug_mismatch` didn't produce the expected `Debug` output")]
+                #[expect_debug("Even(2)")]
+                (4)
+            },
+        }
+    }
+
+    // Property cases construct a value from every input
+    test_gen! {
+        struct Even::doubled check = Even::is_even => {
+            doubled: property {
+                (any::<u16>())
+            },
+        }
+    }
+
+    test_gen! {
+        struct self::Even check = self::Even::assert_even => {
+            panics: {
+                #[should_panic(expected = "assertion")]
+                (3)
+            },
+        }
+    }
+
+    // Values are returned by cases specifying a return type
+    test_gen! {
+        struct ExitCode::from -> ExitCode => {
+            success: {
+                (0)
+            },
+        }
+    }
+}
+
+mod const_cases {
+    use super::*;
+
+    const fn scale(factor: u32, value: u32) -> u32 {
+        factor * value
+    }
+
+    test_gen! {
+        const fn scale => {
+            zero: {
+                (0, 2) == 0
+            },
+            one: {
+                (1, 2) == 2
+            },
+            // Cases extending another also expect the value it expects
+            swapped: extends one {
+                0 = 2,
+                1 = 1,
+            },
+            disabled: {
+                #[cfg(any())]
+                (1, 2) == 3
+            },
+        }
+    }
+
+    test_gen! {
+        const fn scale (3) => {
+            three: {
+                (1) == 3
+            },
+        }
+    }
+
+    struct Span {
+        start: u32,
+        end: u32,
+    }
+
+    const fn is_empty(span: Span) -> bool {
+        span.start == span.end
+    }
+
+    const EMPTY: Span = Span { start: 4, end: 4 };
+
+    test_gen! {
+        const fn is_empty with Span { ..EMPTY } => {
+            start: {
+                start: 4
+            },
+            end: {
+                end: 4
+            },
+        }
+    }
+}
+
+mod compile_fail {
+    use super::*;
+
+    // Compile-fail cases only produce a doctest, so the arguments of the case
+    // aren't compiled as part of the block, but must still refer to items through `crate`
+    test_gen! {
+        fn crate::bool_panic => {
+            compiles: {
+                (true)
+            },
+            mismatched: {
+                #[compile_fail(E0308)]
+                ("true")
+            },
+            undefined: {
+                #[compile_fail]
+                #[cfg(any())]
+                (crate::undefined)
+            },
+        }
+    }
+}
+
+mod generate {
+    use super::*;
+
+    // Nightly benchmarks require a nightly toolchain, so only tests are produced here,
+    // as they are by default
+    test_gen! {
+        #[generate(tests)]
+        fn bool_panic => {
+            tested: {
+                (true)
+            },
+            tested_should_panic: {
+                #[should_panic]
+                (false)
+            },
+        }
+    }
+
+    // Criterion benchmarks are registered by a function named after the helper function,
+    // which is run by `criterion_main!` in a target without the default harness,
+    // whilst the tests of every case are still produced alongside it
+    test_gen! {
+        #[generate(tests, benches = criterion)]
+        fn bool_panic => {
+            benched: {
+                (true)
+            },
+            tested_only: {
+                #[should_panic]
+                (false)
+            },
+        }
+    }
+
+    #[test]
+    fn bool_panic_benches_run() {
+        let mut criterion = criterion::Criterion::default()
+            .sample_size(10)
+            .warm_up_time(std::time::Duration::from_millis(1))
+            .measurement_time(std::time::Duration::from_millis(1));
+
+        bool_panic_benches(&mut criterion);
+    }
+}
+
+mod budget {
+    use super::*;
+    use std::{thread, time::Duration};
+
+    fn sleep_ms(ms: u64) {
+        thread::sleep(Duration::from_millis(ms));
+    }
+
+    test_gen! {
+        #[budget(ms = 1000, iterations = 3)]
+        fn sleep_ms => {
+            within: {
+                (1)
+            },
+            exceeded: {
+                #[budget(ms = 1, iterations = 3)]
+                #[should_panic(expected = "case `exceeded` exceeded its budget of 1ms, over 3 iteration(s)")]
+                (20)
+            },
+        }
+    }
+
+    fn short_sleep(ms: u64) -> Result<(), String> {
+        sleep_ms(ms);
+        (ms <= 5)
+            .then_some(())
+            .ok_or_else(|| format!("slept for {}ms", ms))
+    }
+
+    // The output of the helper function is still returned after it's timed
+    test_gen! {
+        #[budget(us = 100_000)]
+        fn short_sleep -> Result<(), String> => {
+            returned: {
+                (0)
+            },
+            returned_short: {
+                (5)
+            },
+        }
+    }
+}
+
+mod timeout {
+    use super::*;
+    use std::{error::Error, thread, time::Duration};
+
+    fn sleep_ms(ms: u64) {
+        thread::sleep(Duration::from_millis(ms));
+    }
+
+    fn sleep_ms_result(ms: u64) -> Result<(), Box<dyn Error>> {
+        sleep_ms(ms);
+        Ok(())
+    }
+
+    fn assert_positive(value: i16) {
+        assert!(value > 0, "{} isn't positive", value);
+    }
+
+    test_gen! {
+        #[timeout(secs = 5)]
+        fn sleep_ms => {
+            finished: {
+                (1)
+            },
+            timed_out: {
+                #[timeout(ms = 50)]
+                #[should_panic(expected = "case `timed_out` timed out after 50ms")]
+                (10_000)
+            },
+        }
+    }
+
+    // Panics and returned values of the worker thread are propagated to the test
+    test_gen! {
+        #[timeout(secs = 5)]
+        fn assert_positive => {
+            positive: {
+                (1)
+            },
+            negative: {
+                #[should_panic(expected = "-1 isn't positive")]
+                (-1)
+            },
+            positive_property: property {
+                (1..=i16::MAX)
+            },
+            negative_property: property {
+                #[should_panic(expected = "property case `negative_property` failed on input")]
+                (i16::MIN..=0)
+            },
+        }
+    }
+
+    test_gen! {
+        #[timeout(secs = 5)]
+        fn sleep_ms_result -> Result<(), Box<dyn Error>> => {
+            returned: {
+                (1)
+            },
+        }
+    }
+
+    test_gen! {
+        fn Into::into => {
+            exit_code: {
+                #[timeout(secs = 5)]
+                (0) -> ExitCode
+            },
+        }
+    }
+}
+
+mod suites {
+    use super::*;
+
+    fn zero_is_default<T: Default + From<u8> + PartialEq + std::fmt::Debug>() {
+        assert_eq!(T::default(), T::from(0));
+    }
+
+    fn max_is_not_default<T: Default + From<u8> + PartialEq + std::fmt::Debug>() {
+        assert_ne!(T::default(), T::from(u8::MAX));
+    }
+
+    mod checks {
+        pub fn clones_equal<T: Default + Clone + PartialEq>() -> Result<(), &'static str> {
+            let value = T::default();
+            (value.clone() == value)
+                .then_some(())
+                .ok_or("clone isn't equal")
+        }
+    }
+
+    test_gen_suite! {
+        name = from_u8;
+
+        fn zero_is_default,
+        fn self::max_is_not_default as max,
+        #[ignore]
+        fn checks::clones_equal -> Result<(), &'static str>,
+    }
+
+    test_gen! {
+        instantiate suite from_u8 for [
+            u16,
+            i32,
+            #[cfg(any())]
+            Disabled,
+            std::primitive::u64 as primitive_u64,
+        ]
+    }
+
+    mod nested {
+        use super::*;
+
+        // Checks are resolved relative to the module the suite is instantiated in,
+        // so are only required to be in scope there
+        fn zero_is_default<T: From<u8> + PartialEq + std::fmt::Debug>() {
+            assert_eq!(T::from(0), T::from(0));
+        }
+
+        test_gen! {
+            #[cfg_attr(miri, ignore)]
+            instantiate suite from_u8 for [u8 as byte];
+        }
+
+        // Checks starting with `super` are also resolved from the module the suite is instantiated in
+        test_gen_suite! {
+            name = from_parent;
+
+            fn super::checks::clones_equal -> Result<(), &'static str>,
+        }
+
+        test_gen! {
+            instantiate suite from_parent for [u32];
+        }
+    }
+}
+
+mod assert_impl {
+    use super::*;
+    use std::fmt::Debug;
+
+    struct Unsized<T: ?Sized>(T);
+
+    test_gen! {
+        assert_impl { Send + Sync + Debug + 'static } for [
+            u8,
+            String as owned,
+            Vec<u8>,
+            std::collections::HashMap<u8, u8>,
+            &'static str as static_str,
+            str,
+            #[cfg(any())]
+            std::rc::Rc<u8>,
+            // Names only need to be unique amongst types with the same `cfg` attributes
+            #[cfg(target_pointer_width = "64")]
+            Vec<u64>,
+            #[cfg(not(target_pointer_width = "64"))]
+            Vec<u32>,
+        ]
+    }
+
+    test_gen! {
+        #[cfg(not(any()))]
+        assert_impl { Send } for [Unsized<[u8]> as unsized_slice, dyn Debug + Send as debug];
+    }
+}
+
+mod data_files {
+    use super::*;
+
+    fn assert_char_count(input: &str, expected: usize, count: u8) {
+        assert_eq!(input.chars().count(), expected);
+        assert_eq!(usize::from(count), expected);
+    }
+
+    test_gen! {
+        fn assert_char_count => {
+            cases from tsv "tests/data/char_count.tsv";
+
+            own_case: {
+                ("\u{1F980}", 1, 1)
+            },
+        }
+    }
+
+    fn assert_halved(value: f64, halved: f32) {
+        assert_eq!(value / 2.0, f64::from(halved));
+    }
+
+    // Whole numbers are accepted as floats
+    test_gen! {
+        fn assert_halved => {
+            cases from csv "tests/data/halves.csv";
+        }
+    }
+
+    mod glob {
+        use super::*;
+
+        fn assert_len(expected: usize, contents: &[u8]) {
+            assert_eq!(contents.len(), expected);
+        }
+
+        test_gen! {
+            fn assert_len (4) => {
+                cases from glob "./tests/fixtures/lines/o?e.txt" as bytes;
+            }
+        }
+
+        test_gen! {
+            fn assert_len (0) => {
+                cases from glob "tests/fixtures/**/empty.*" as bytes;
+            }
+        }
+    }
+
+    mod golden {
+        use super::*;
+
+        fn reverse_lines(input: &str) -> String {
+            input
+                .lines()
+                .rev()
+                .map(|line| format!("{}\n", line))
+                .collect()
+        }
+
+        fn line_count(prefix: &str, input: &[u8]) -> String {
+            format!(
+                "{}{}\n",
+                prefix,
+                input.iter().filter(|&&b| b == b'\n').count()
+            )
+        }
+
+        test_gen! {
+            fn reverse_lines => {
+                cases from golden "tests/golden/reverse/*.in";
+
+                inline: {
+                    #[golden("tests/golden/reverse/three.out")]
+                    ("1\n2\n3\n")
+                },
+            }
+        }
+
+        test_gen! {
+            fn line_count ("lines: ") => {
+                cases from golden "tests/golden/line_count/*.txt" as bytes;
+            }
+        }
+    }
+
+    mod regressions {
+        use super::*;
+
+        test_gen! {
+            fn assert_char_count => {
+                regressions "tests/regressions/char_count.txt";
+                // Cases are named after each file, so blocks can replay several
+                regressions "tests/regressions/char_count_ascii.txt";
+                cases from tsv "tests/data/char_count.tsv";
+
+                regressions: {
+                    ("regressions", 11, 11)
+                },
+            }
+        }
+    }
+
+    #[cfg(feature = "json")]
+    mod json {
+        use super::*;
+
+        fn assert_char_count(input: &str, expected: usize) {
+            assert_eq!(input.chars().count(), expected);
+        }
+
+        test_gen! {
+            fn assert_char_count => {
+                cases from json "tests/data/char_count.json";
+            }
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    mod toml {
+        use super::*;
+
+        struct Case {
+            input: &'static str,
+            expected: usize,
+        }
+
+        fn assert_char_count(case: Case) {
+            assert_eq!(case.input.chars().count(), case.expected);
+        }
+
+        test_gen! {
+            fn assert_char_count => {
+                cases from toml "tests/data/char_count.toml" as Case;
+            }
+        }
+    }
+}
+
+fn bool_panic(switch: bool) {
+    assert!(switch);
+}
+
+mod snapshots {
+    use super::*;
+
+    fn split(input: &str) -> Vec<&str> {
+        input.split(',').collect()
+    }
+
+    test_gen! {
+        fn split -> snapshot => {
+            single: {
+                ("one")
+            },
+            several: {
+                ("one,two,three")
+            },
+            r#type: {
+                ("")
+            },
+        }
+    }
+
+    test_gen! {
+        fn str::len -> snapshot(Display) => {
+            displayed: {
+                ("one,two")
+            },
+        }
+    }
+}
+
+mod differential {
+    use super::*;
+
+    fn checked_div(lhs: u32, rhs: u32) -> Option<u32> {
+        lhs.checked_div(rhs)
+    }
+
+    fn wrapping_div(lhs: u32, rhs: u32) -> Option<u32> {
+        (rhs != 0).then(|| lhs / rhs)
+    }
+
+    fn truncated_div(lhs: u32, rhs: u32) -> Option<u32> {
+        Some(lhs / rhs.max(1))
+    }
+
+    test_gen! {
+        fn checked_div vs wrapping_div (12) -> snapshot => {
+            by_one: {
+                (1)
+            },
+            by_five: {
+                (5)
+            },
+            by_zero: {
+                (0)
+            },
+        }
+    }
+
+    test_gen! {
+        fn checked_div vs truncated_div => {
+            diverged: {
+                #[should_panic(expected = "case `diverged` diverged from the reference implementation")]
+                (1, 0)
+            },
+            agreed: {
+                (4, 2)
+            },
+        }
+    }
+}
+
+mod roundtrip {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn encode(point: &Point) -> String {
+        format!("{},{}", point.x, point.y)
+    }
+
+    fn decode(encoded: &str) -> Option<Point> {
+        let (x, y) = encoded.split_once(',')?;
+
+        Some(Point {
+            x: x.trim().parse().ok()?,
+            y: y.trim().parse().ok()?,
+        })
+    }
+
+    fn encode_lossy(point: &Point) -> String {
+        format!("{},0", point.x)
+    }
+
+    test_gen! {
+        roundtrip fn encode, fn decode => {
+            origin: {
+                (Point { x: 0, y: 0 })
+            },
+            negative: {
+                (Point { x: -3, y: 4 })
+            },
+            canonical: {
+                #[canonical]
+                ("1,2")
+            },
+            not_canonical: {
+                #[canonical]
+                #[should_panic(expected = "case `not_canonical` isn't canonical")]
+                ("1, 2")
+            },
+            undecodable: {
+                #[canonical]
+                #[should_panic(expected = "case `undecodable` couldn't be decoded: None")]
+                ("1")
+            },
+        }
+    }
+
+    test_gen! {
+        roundtrip fn encode_lossy, fn decode -> snapshot(Display) => {
+            encoded: {
+                (Point { x: 1, y: 0 })
+            },
+        }
+    }
+
+    test_gen! {
+        roundtrip fn encode_lossy, fn decode => {
+            lossy: {
+                #[should_panic(expected = "case `lossy` doesn't survive a round trip")]
+                (Point { x: 1, y: 2 })
+            },
+        }
+    }
+
+    fn encode_le(value: &u32) -> [u8; 4] {
+        value.to_le_bytes()
+    }
+
+    fn decode_le(encoded: &[u8]) -> u32 {
+        u32::from_le_bytes(encoded.try_into().unwrap())
+    }
+
+    // Decoding functions can also return the decoded value itself
+    test_gen! {
+        roundtrip fn encode_le, fn decode_le => {
+            zero: {
+                (0)
+            },
+            max: {
+                (u32::MAX)
+            },
+            canonical_le: {
+                #[canonical]
+                ([1, 0, 0, 0])
+            },
+        }
+    }
+
+    // Budgets and benchmarks call the encoding function alone, with a reference to the value,
+    // as the test of the case does
+    test_gen! {
+        #[generate(tests, benches = criterion)]
+        #[budget(ms = 1000, iterations = 3)]
+        roundtrip fn encode, fn decode => {
+            budgeted: {
+                (Point { x: 1, y: 2 })
+            },
+            budgeted_negative: {
+                #[budget(ms = 1000)]
+                (Point { x: 0, y: -1 })
+            },
+        }
+    }
+}
+
+mod property {
+    use super::*;
+
+    fn assert_in_range(value: i8, low: i8, high: i8) {
+        assert!(
+            (low..=high).contains(&value),
+            "{} isn't within {}..={}",
+            value,
+            low,
+            high
+        );
+    }
+
+    fn always_false(_: u8) -> bool {
+        false
+    }
+
+    fn error(value: char) -> Result<(), char> {
+        Err(value)
+    }
+
+    test_gen! {
+        fn assert_in_range => {
+            in_range: property {
+                (-5..=5, just(-5), just(5))
+            },
+            in_range_exclusive: extends in_range {
+                0 = -5..6
+            },
+            out_of_range: property(64) {
+                #[should_panic(expected = "property case `out_of_range` failed on input")]
+                (any::<i8>(), just(-5), just(5))
+            },
+            fixed: {
+                (1, -5, 5)
+            },
+        }
+    }
+
+    test_gen! {
+        fn always_false => {
+            returns_false: property {
+                #[should_panic(expected = "which returned `false`")]
+                (any::<u8>())
+            },
+        }
+    }
+
+    test_gen! {
+        fn error => {
+            returns_error: property {
+                #[should_panic(expected = "which returned `Err(")]
+                ('a'..='z')
+            },
+        }
+    }
+
+    fn short(items: Vec<u16>) {
+        assert!(items.len() < 3, "{} items", items.len());
+    }
+
+    test_gen! {
+        fn short => {
+            shrunk: property {
+                #[should_panic(expected = "which panicked with `3 items`:\n\n\
+                    input: (vec![0, 0, 0])\n\n\
+                    add it to the block as a case:\n\n    \
+                    shrunk_regression: { (vec![0, 0, 0]) },")]
+                (vec_of(any::<u16>(), 3..=500))
+            },
+        }
+    }
+
+    fn len(items: Vec<u16>) -> usize {
+        items.len()
+    }
+
+    fn count(items: Vec<u16>) -> usize {
+        items.iter().filter(|_| true).count()
+    }
+
+    test_gen! {
+        fn len vs count => {
+            counted: property {
+                (vec_of(any::<u16>(), 0..=16))
+            },
+        }
+    }
+
+    fn encode(value: &f64) -> String {
+        value.to_string()
+    }
+
+    fn decode(encoded: &str) -> Result<f64, std::num::ParseFloatError> {
+        encoded.parse()
+    }
+
+    test_gen! {
+        roundtrip fn encode, fn decode => {
+            floats: property {
+                (-1e9..1e9)
+            },
+        }
+    }
+
+    // Only cases specifying `proptest` strategies refer to `proptest`
+    mod proptest {
+        use super::*;
+
+        fn assert_lowercase(value: String) {
+            assert_eq!(value, value.to_lowercase());
+        }
+
+        test_gen! {
+            fn assert_lowercase => {
+                lowercase: property {
+                    (proptest("[a-z]{0,16}"))
+                },
+            }
+        }
+    }
 }